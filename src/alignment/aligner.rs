@@ -1,5 +1,5 @@
 use std::cmp;
-use ndarray::Array2;
+use ndarray::{s, Array2};
 
 use crate::error::{BioError, Result};
 use crate::ds::sequence::Sequence;
@@ -259,6 +259,133 @@ impl SequenceAligner  {
         Ok((alignment_score, s1_aligned, s2_aligned))
     }
 
+    /// Compute the fitting alignment of `seq2` within `seq1`, restricted to a diagonal band
+    ///
+    /// Only cells of the dynamic programming matrix on diagonals `i - j` within `[-band, seq1.len() - seq2.len() + band]`
+    /// are evaluated, reducing the cost to `O(seq2.len() * (seq1.len() - seq2.len() + 2 * band))`.
+    /// Intended for verifying candidate locations, where `seq1` is a window of the reference
+    /// slightly larger than `seq2`. Besides the alignment, the offset in `seq1` at which the alignment starts is returned.
+    ///
+    /// # Arguments
+    /// * `seq1`, `seq2` - sequences to align, `seq2` is fitted into `seq1`
+    /// * `score` - scoring function to be used during alignment
+    /// * `a` - penalty for opening a gap
+    /// * `b` - penalty for extending a gap
+    /// * `band` - number of diagonals outside the minimal band, bounding the net number of insertions or deletions
+    ///
+    /// # Example
+    /// ```
+    /// use biogarden::alignment::aligner::SequenceAligner;
+    /// use biogarden::alignment::score::unit;
+    /// use biogarden::ds::sequence::Sequence;
+    ///
+    /// let mut aligner = SequenceAligner::new();
+    ///
+    /// let s1 = Sequence::from("TTTGATTACAGGG");
+    /// let s2 = Sequence::from("GATACA");
+    /// let (align_score, start, s1_aligned, s2_aligned) =
+    ///     aligner.banded_fitting_alignment(&s1, &s2, &unit, -1, -1, 2).unwrap();
+    ///
+    /// assert_eq!(align_score, 5);
+    /// assert_eq!(start, 3);
+    /// assert_eq!(s1_aligned, Sequence::from("GATTACA"));
+    /// assert_eq!(s2_aligned, Sequence::from("GAT-ACA"));
+    /// ```
+    pub fn banded_fitting_alignment(&mut self, seq1: &Sequence, seq2: &Sequence,
+                                    score: &dyn Fn(&u8, &u8) -> i32, a: i32, b: i32, band: usize) -> Result<(i32, usize, Sequence, Sequence)> {
+
+        if a > 0 || b > 0 {
+            return Err(BioError::InvalidArgumentRange);
+        }
+
+        if seq1.len() < seq2.len() || seq2.is_empty() {
+            return Err(BioError::InvalidInputSize);
+        }
+
+        // Allocate a larger buffer if sequences cannot fit
+        if seq1.len() >= self.buffer_size.0 || seq2.len() >= self.buffer_size.1 {
+            self.resize_buffers(seq1.len()+1, seq2.len()+1);
+        }
+
+        // Score of cells outside of the band, low enough to never be chosen, high enough to never overflow
+        let unreachable = i32::MIN / 2;
+        let slack = seq1.len() - seq2.len() + band;
+        let columns = |i: usize| (cmp::max(1, i.saturating_sub(slack)), cmp::min(seq2.len(), i + band));
+
+        // Initialize uppermost row, gaps at the start of `seq2` are penalized
+        for j in 0..cmp::min(seq2.len(), band) + 1 {
+            self.m[[0,j]] = if j == 0 { 0 } else { a + (j as i32 - 1) * b };
+            self.x[[0,j]] = unreachable;
+            self.y[[0,j]] = unreachable;
+            self.m_trace[[0,j]] = b'Y';
+            self.y_trace[[0,j]] = if j > 1 { b'I' } else { b'M' };
+        }
+        if band < seq2.len() {
+            self.m[[0,band+1]] = unreachable;
+            self.x[[0,band+1]] = unreachable;
+            self.y[[0,band+1]] = unreachable;
+        }
+
+        for i in 1..(seq1.len() + 1) {
+            let (lo, hi) = columns(i);
+            // Alignment may start anywhere within `seq1`, as long as it is reachable within the band
+            self.m[[i,lo-1]] = if lo == 1 { 0 } else { unreachable };
+            self.x[[i,lo-1]] = unreachable;
+            self.y[[i,lo-1]] = unreachable;
+            self.m_trace[[i,lo-1]] = b'X';
+            if hi < seq2.len() {
+                self.m[[i,hi+1]] = unreachable;
+                self.x[[i,hi+1]] = unreachable;
+                self.y[[i,hi+1]] = unreachable;
+            }
+
+            for j in lo..(hi + 1) {
+                // Establish optimal action to perform with regards to gaps in x (seq1)
+                self.x[[i,j]] = cmp::max(self.m[[i-1,j]].saturating_add(a), self.x[[i-1,j]].saturating_add(b));
+                self.x_trace[[i,j]] = if self.x[[i,j]] == self.m[[i-1,j]].saturating_add(a) { b'M' } else { b'I' };
+
+                // Establish optimal action to perform with regards to gaps in y (seq2)
+                self.y[[i,j]] = cmp::max(self.m[[i,j-1]].saturating_add(a), self.y[[i,j-1]].saturating_add(b));
+                self.y_trace[[i,j]] = if self.y[[i,j]] == self.m[[i,j-1]].saturating_add(a) { b'M' } else { b'I' };
+
+                // Find optimal action from: Replace/Match | Gap in X | Gap in Y
+                let maximum = cmp::max(self.m[[i-1,j-1]].saturating_add(score(&seq1[i-1], &seq2[j-1])),
+                                       cmp::max(self.x[[i,j]], self.y[[i,j]]));
+
+                // Update main trace depending on optimal action
+                if maximum == self.y[[i,j]] {
+                    self.m_trace[[i,j]] = b'Y';
+                }
+                else if maximum == self.x[[i,j]] {
+                    self.m_trace[[i,j]] = b'X';
+                }
+                else {
+                    self.m_trace[[i,j]] = b'R';
+                }
+
+                self.m[[i,j]] = maximum;
+            }
+        }
+
+        // Compute position and value of maximum alignment score alongside last column, within the band
+        let mut max_pos = (0, seq2.len());
+        let mut alignment_score = i32::MIN;
+        for i in seq2.len().saturating_sub(band)..(seq1.len() + 1) {
+            let (lo, hi) = columns(i);
+            if (lo..hi + 1).contains(&seq2.len()) && self.m[[i,seq2.len()]] > alignment_score {
+                alignment_score = self.m[[i,seq2.len()]];
+                max_pos = (i, seq2.len());
+            }
+        }
+
+        // Backtrace to find optimal alignment, `k` ends at the start of the alignment in `seq1`
+        let mut k = max_pos.0;
+        let mut l = max_pos.1;
+        let (s1_aligned, s2_aligned) = self.backtrack_gap_states(seq1, seq2, &mut k, &mut l);
+
+        Ok((alignment_score, k, s1_aligned, s2_aligned))
+    }
+
     /// Compute the overlap alignment of two genetic strings
     ///
     /// Maximize the local alignment score over all substrings of `seq1` and `seq2`, 
@@ -296,7 +423,7 @@ impl SequenceAligner  {
         }
 
         // Initialize score buffer
-        self.m.fill(0);
+        self.m.slice_mut(s![..seq1.len()+1, ..seq2.len()+1]).fill(0);
         // Initialize trace buffer boundaries (used for backtracking)
         self.m_trace.column_mut(0).fill(b'X');
         self.m_trace.row_mut(0).fill(b'Y');
@@ -307,6 +434,7 @@ impl SequenceAligner  {
         // Compute position and value of maximum alignment score alongside last row
         let maximum = self.m.row(seq1.len())
                             .indexed_iter()
+                            .take(seq2.len() + 1)
                             .fold((0,i32::MIN), |max, x| if *x.1 >= max.1 { (x.0, *x.1) } else { max });
         let max_pos = (seq1.len(), maximum.0);
         let alignment_score = maximum.1;
@@ -591,6 +719,55 @@ impl SequenceAligner  {
         (s1_aligned, s2_aligned)
     }
 
+    // Backtrack until `seq2` is consumed, following gap traces of the cell the gap ends in.
+    // Every letter is attributed to the buffer it was scored in, so the alignment reproduces the reported score.
+    fn backtrack_gap_states(&self, seq1: &Sequence, seq2: &Sequence, k: &mut usize, l: &mut usize) -> (Sequence, Sequence) {
+
+        let mut s1_aligned = Sequence::new();
+        let mut s2_aligned = Sequence::new();
+        let mut curtrace : u8 = b'M';
+
+        while *l != 0 {
+            match curtrace {
+                b'X' => {
+                    // Insert/Delete in X, Gap in Y, return to M where the gap has been opened
+                    s1_aligned.push(seq1[*k-1]);
+                    s2_aligned.push(b'-');
+                    if self.x_trace[[*k,*l]] == b'M' {
+                        curtrace = b'M';
+                    }
+                    *k -= 1;
+                }
+                b'Y' => {
+                    // Insert/Delete in Y, Gap in X, return to M where the gap has been opened
+                    s1_aligned.push(b'-');
+                    s2_aligned.push(seq2[*l-1]);
+                    if self.y_trace[[*k,*l]] == b'M' {
+                        curtrace = b'M';
+                    }
+                    *l -= 1;
+                }
+                _ => {
+                    match self.m_trace[[*k,*l]] {
+                        b'R' => {
+                            // Replace/Match
+                            s1_aligned.push(seq1[*k-1]);
+                            s2_aligned.push(seq2[*l-1]);
+                            *k -= 1;
+                            *l -= 1;
+                        }
+                        trace => curtrace = trace,
+                    }
+                }
+            }
+        }
+
+        s1_aligned.reverse();
+        s2_aligned.reverse();
+
+        (s1_aligned, s2_aligned)
+    }
+
     fn resize_buffers(&mut self, dim1: usize, dim2: usize) {
         self.buffer_size = (dim1, dim2);
         self.m = Array2::zeros(self.buffer_size);
@@ -0,0 +1,297 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use crate::error::{BioError, Result};
+use crate::alignment::aligner::SequenceAligner;
+use crate::alignment::score::unit;
+use crate::ds::fm_index::FmIndex;
+use crate::ds::sequence::{Sequence, Strand};
+use crate::ds::tile::Tile;
+use crate::processing::transformers::reverse_complement;
+
+/// Location of a read on the reference, as reported by `ReadMapper`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    /// Identifier of the mapped read
+    pub read_id: Option<String>,
+    /// Index of the reference sequence the read has been mapped to
+    pub reference: usize,
+    /// Identifier of the reference sequence
+    pub reference_id: Option<String>,
+    /// Leftmost position of the alignment on the forward strand of the reference (0-based)
+    pub position: usize,
+    /// Strand of the reference, which the read originates from
+    pub strand: Strand,
+    /// Alignment score
+    pub score: i32,
+    /// Mapping quality in the range 0 - 60, where 0 means the read maps equally well to multiple locations
+    pub mapq: u8,
+    /// Alignment of the read (reverse complemented for the reverse strand) against the reference in CIGAR notation
+    pub cigar: String,
+}
+
+/// Map short reads against a reference using FM-index seeds and banded alignment
+///
+/// The reference is indexed once with an `FmIndex`. For every read and its reverse complement
+/// non-overlapping k-mer seeds are located in the index, where each hit votes for a diagonal (reference offset of the read start).
+/// Candidate locations are verified with a banded fitting alignment of the read against a window of the reference,
+/// and the best scoring location is reported together with its mapping quality and CIGAR string.
+///
+/// Seeds occurring more often than `max_occurrences` times are ignored, as they stem from repetitive regions.
+/// Scoring defaults to `unit` with a gap opening penalty of -2 and extension penalty of -1.
+///
+/// # Example
+/// ```
+/// use biogarden::alignment::mapper::ReadMapper;
+/// use biogarden::ds::sequence::{Sequence, Strand};
+/// use biogarden::ds::tile::Tile;
+/// use biogarden::processing::transformers::complement_dna;
+///
+/// let mut reference = Tile::new();
+/// reference.push(Sequence::from("TTAGCCGATCGTACGGATCCATGCAAGTCGATTGCAGTACCGTAGGCATTCAGG"));
+///
+/// let mut mapper = ReadMapper::new(&reference, 8).unwrap();
+///
+/// // Read with a single mismatch
+/// let read = Sequence::from("GATCGTACGGTTCCATGCAAGT");
+/// let mapping = mapper.map_read(&read).unwrap().unwrap();
+/// assert_eq!(mapping.position, 6);
+/// assert_eq!(mapping.strand, Strand::Forward);
+/// assert_eq!(mapping.cigar, "22M");
+///
+/// // Read sequenced from the opposite strand, with a deletion
+/// let read = complement_dna(Sequence::from("GCAGTACCGAGGCATTCAGG"));
+/// let mapping = mapper.map_read(&read).unwrap().unwrap();
+/// assert_eq!(mapping.position, 33);
+/// assert_eq!(mapping.strand, Strand::Reverse);
+/// assert_eq!(mapping.cigar, "9M1D11M");
+/// ```
+pub struct ReadMapper {
+    // Indexed reference sequences
+    reference: Tile,
+    index: FmIndex,
+    // Seeding parameters
+    seed_len: usize,
+    max_occurrences: usize,
+    // Verification parameters
+    band: usize,
+    max_candidates: usize,
+    min_score: i32,
+    score: fn(&u8, &u8) -> i32,
+    gap_open: i32,
+    gap_extend: i32,
+    aligner: SequenceAligner,
+}
+
+// Candidate location of a read, before verification
+struct Candidate {
+    reference: usize,
+    diagonal: isize,
+    strand: Strand,
+    votes: usize,
+}
+
+impl ReadMapper {
+
+    /// Index the reference sequences for mapping reads with seeds of length `seed_len`
+    ///
+    /// # Arguments
+    /// * `reference` - reference sequences, e.g. loaded with `io::fasta`
+    /// * `seed_len` - length of exact k-mer seeds, reads shorter than `seed_len` cannot be mapped
+    pub fn new(reference: &Tile, seed_len: usize) -> Result<ReadMapper> {
+        if seed_len == 0 || reference.is_empty() {
+            return Err(BioError::InvalidInputSize);
+        }
+        Ok(ReadMapper {
+            reference: reference.clone(),
+            index: FmIndex::from_tile(reference, 16),
+            seed_len,
+            max_occurrences: 64,
+            band: 8,
+            max_candidates: 16,
+            min_score: 1,
+            score: unit,
+            gap_open: -2,
+            gap_extend: -1,
+            aligner: SequenceAligner::new(),
+        })
+    }
+
+    /// Ignore seeds with more than `max_occurrences` hits in the reference
+    pub fn with_max_occurrences(mut self, max_occurrences: usize) -> Self {
+        self.max_occurrences = max_occurrences;
+        self
+    }
+
+    /// Width of the alignment band, bounding the net number of insertions or deletions in a read
+    pub fn with_band(mut self, band: usize) -> Self {
+        self.band = band;
+        self
+    }
+
+    /// Maximum number of candidate locations per strand to verify with alignment
+    pub fn with_max_candidates(mut self, max_candidates: usize) -> Self {
+        self.max_candidates = max_candidates;
+        self
+    }
+
+    /// Discard alignments scoring less than `min_score`
+    pub fn with_min_score(mut self, min_score: i32) -> Self {
+        self.min_score = min_score;
+        self
+    }
+
+    /// Scoring function and gap penalties used during verification, see `SequenceAligner`
+    pub fn with_scoring(mut self, score: fn(&u8, &u8) -> i32, a: i32, b: i32) -> Result<Self> {
+        if a > 0 || b > 0 {
+            return Err(BioError::InvalidArgumentRange);
+        }
+        self.score = score;
+        self.gap_open = a;
+        self.gap_extend = b;
+        Ok(self)
+    }
+
+    /// Index backing the mapper
+    pub fn index(&self) -> &FmIndex {
+        &self.index
+    }
+
+    /// Map a single read, returning `None` if no location with sufficient score has been found
+    pub fn map_read(&mut self, read: &Sequence) -> Result<Option<Mapping>> {
+        if read.len() < self.seed_len {
+            return Ok(None);
+        }
+
+        // Collect candidate locations on both strands
+        let rev_read = reverse_complement(read);
+        let mut candidates = self.candidates(read, Strand::Forward);
+        candidates.extend(self.candidates(&rev_read, Strand::Reverse));
+
+        // Verify candidates with banded alignment, keeping distinct locations only
+        let mut verified = Vec::<Mapping>::new();
+        for candidate in &candidates {
+            let query = match candidate.strand {
+                Strand::Forward => read,
+                Strand::Reverse => &rev_read,
+            };
+            if let Some(mapping) = self.verify(query, candidate)? {
+                if !verified.iter().any(|m| m.reference == mapping.reference
+                                         && m.position == mapping.position
+                                         && m.strand == mapping.strand) {
+                    verified.push(mapping);
+                }
+            }
+        }
+
+        // Report best location, mapping quality depends on the score difference to the runner-up
+        verified.sort_by_key(|m| cmp::Reverse(m.score));
+        let mut verified = verified.into_iter();
+        let mut best = match verified.next() {
+            Some(best) if best.score >= self.min_score => best,
+            _ => return Ok(None),
+        };
+        best.mapq = match verified.next() {
+            Some(second) if second.score > 0 && best.score > 0 => {
+                (60.0 * (best.score - second.score) as f64 / best.score as f64).round().clamp(0.0, 60.0) as u8
+            }
+            _ => 60,
+        };
+        best.read_id = read.id.clone();
+        Ok(Some(best))
+    }
+
+    /// Map all reads of a tile, e.g. loaded with `io::fasta` or `io::fastq`, unmapped reads yield `None`
+    pub fn map_tile(&mut self, reads: &Tile) -> Result<Vec<Option<Mapping>>> {
+        reads.into_iter().map(|read| self.map_read(read)).collect()
+    }
+
+    // Vote for diagonals of a query with exact seed hits, then merge votes of neighbouring diagonals
+    fn candidates(&self, query: &Sequence, strand: Strand) -> Vec<Candidate> {
+
+        // Non-overlapping seeds, with the last seed aligned to the end of the query
+        let mut seed_offsets: Vec<usize> = (0..query.len() - self.seed_len + 1).step_by(self.seed_len).collect();
+        if seed_offsets.last() != Some(&(query.len() - self.seed_len)) {
+            seed_offsets.push(query.len() - self.seed_len);
+        }
+
+        let mut votes = HashMap::<(usize, isize), usize>::new();
+        for offset in seed_offsets {
+            let seed = &query[offset..offset + self.seed_len];
+            if self.index.count(seed) > self.max_occurrences {
+                continue;
+            }
+            for pos in self.index.locate(seed) {
+                let (seq_idx, seq_pos) = self.index.resolve(pos);
+                *votes.entry((seq_idx, seq_pos as isize - offset as isize)).or_insert(0) += 1;
+            }
+        }
+
+        // Diagonals within the band are merged into a single candidate
+        let mut diagonals: Vec<((usize, isize), usize)> = votes.into_iter().collect();
+        diagonals.sort_unstable();
+        let mut candidates = Vec::<Candidate>::new();
+        for ((reference, diagonal), count) in diagonals {
+            match candidates.last_mut() {
+                Some(last) if last.reference == reference && diagonal - last.diagonal <= self.band as isize => {
+                    last.votes += count;
+                }
+                _ => candidates.push(Candidate { reference, diagonal, strand, votes: count }),
+            }
+        }
+
+        // Only the best supported candidates are verified
+        candidates.sort_by_key(|c| cmp::Reverse(c.votes));
+        candidates.truncate(self.max_candidates);
+        candidates
+    }
+
+    // Align query within a reference window around the candidate diagonal
+    fn verify(&mut self, query: &Sequence, candidate: &Candidate) -> Result<Option<Mapping>> {
+        let target = &self.reference[candidate.reference];
+        let start = cmp::max(0, candidate.diagonal - self.band as isize) as usize;
+        let end = cmp::min(target.len() as isize, candidate.diagonal + (query.len() + 2 * self.band) as isize);
+        if end < (start + query.len()) as isize {
+            return Ok(None);
+        }
+        let window = Sequence::from(&target[start..end as usize]);
+
+        let (score, offset, target_aligned, query_aligned) = self.aligner.banded_fitting_alignment(
+            &window, query, &self.score, self.gap_open, self.gap_extend, self.band)?;
+
+        Ok(Some(Mapping {
+            read_id: None,
+            reference: candidate.reference,
+            reference_id: target.id.clone(),
+            position: start + offset,
+            strand: candidate.strand,
+            score,
+            mapq: 0,
+            cigar: cigar(&target_aligned, &query_aligned),
+        }))
+    }
+}
+
+// Compress an alignment into CIGAR notation: M (match/mismatch), I (insertion in read), D (deletion from read)
+fn cigar(target_aligned: &Sequence, query_aligned: &Sequence) -> String {
+    let mut cigar = String::new();
+    let mut run = 0_usize;
+    let mut prev_op = ' ';
+    for (t, q) in target_aligned.into_iter().zip(query_aligned) {
+        let op = match (t, q) {
+            (b'-', _) => 'I',
+            (_, b'-') => 'D',
+            _ => 'M',
+        };
+        if op != prev_op && run > 0 {
+            cigar.push_str(&format!("{}{}", run, prev_op));
+            run = 0;
+        }
+        prev_op = op;
+        run += 1;
+    }
+    if run > 0 {
+        cigar.push_str(&format!("{}{}", run, prev_op));
+    }
+    cigar
+}
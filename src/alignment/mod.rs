@@ -2,3 +2,5 @@
 pub mod aligner;
 /// Alignment scoring functions
 pub mod score;
+/// Short-read mapping against an indexed reference
+pub mod mapper;
\ No newline at end of file
@@ -4,3 +4,7 @@ pub mod seq;
 pub mod stat;
 /// Mass spectrometry
 pub mod spectro;
+/// MinHash and FracMinHash sketches, alignment-free sequence comparison
+pub mod sketch;
+/// Tandem repeats and microsatellites
+pub mod repeats;
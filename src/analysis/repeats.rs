@@ -0,0 +1,247 @@
+use crate::ds::sequence::Sequence;
+use crate::error::{BioError, Result};
+use crate::processing::kmers::encode_base;
+
+/// Tandem repeat, i.e. consecutive approximate copies of a repeat unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct TandemRepeat {
+    /// Start of the repeat within the sequence
+    pub start: usize,
+    /// End of the repeat within the sequence (exclusive)
+    pub end: usize,
+    /// Length of the repeat unit
+    pub period: usize,
+    /// Consensus repeat unit, in the phase of the first copy
+    pub unit: Sequence,
+    /// Number of copies of the unit, including a partial last copy
+    pub copies: f64,
+    /// Fraction of letters agreeing with the consensus unit
+    pub purity: f64,
+    /// Number of positions differing from the letter one period ahead
+    pub mismatches: usize,
+}
+
+/// Find tandem repeats and microsatellites (short tandem repeats) in genomic sequences
+///
+/// Complexity: O(nP), with `n` - length of the sequence, `P` - largest period
+///
+/// For every period, stretches in which each letter equals the letter one period ahead are found by
+/// comparing every letter with the letter one period ahead, without building an index of the sequence. Stretches of at least two exact copies are extended in both directions
+/// across mismatches, as long as the fraction of mismatching positions stays within `max_mismatch_rate`.
+/// Repeats are reported with a primitive unit, multiples of a repeat reported at a shorter period are skipped.
+/// Repeats of letters other than A, C, G, T/U, such as runs of N, are skipped, letters are compared case-insensitively.
+/// By default, exact microsatellites with periods 1 to 6, at least 3 copies and 10 letters are reported.
+///
+/// # Example
+/// ```
+/// use biogarden::analysis::repeats::TandemRepeatFinder;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// let seq = Sequence::from("GGCATCACACACACACAGTTAGGATTGCATGCATGCATGCTTGCATCC");
+/// let repeats = TandemRepeatFinder::new().find(&seq).unwrap();
+/// assert_eq!(repeats.len(), 2);
+/// assert_eq!((repeats[0].start, repeats[0].end, repeats[0].period), (5, 17, 2));
+/// assert_eq!(repeats[0].unit, Sequence::from("CA"));
+/// assert_eq!(repeats[0].copies, 6.0);
+/// assert_eq!((repeats[1].start, repeats[1].end), (25, 40));
+/// assert_eq!(repeats[1].unit, Sequence::from("TGCA"));
+///
+/// // Tolerate a substitution within the repeat
+/// let seq = Sequence::from("GGCATCACACAGACACAGTTAGG");
+/// let repeats = TandemRepeatFinder::new().with_max_mismatch_rate(0.2).find(&seq).unwrap();
+/// assert_eq!((repeats[0].start, repeats[0].end, repeats[0].mismatches), (5, 17, 2));
+/// assert_eq!(repeats[0].unit, Sequence::from("CA"));
+/// assert!((repeats[0].purity - 11.0 / 12.0).abs() < 1e-9);
+/// ```
+pub struct TandemRepeatFinder {
+    min_period: usize,
+    max_period: usize,
+    min_copies: f64,
+    min_length: usize,
+    max_mismatch_rate: f64,
+}
+
+impl TandemRepeatFinder {
+
+    pub fn new() -> TandemRepeatFinder {
+        TandemRepeatFinder { min_period: 1, max_period: 6, min_copies: 3.0, min_length: 10, max_mismatch_rate: 0.0 }
+    }
+
+    /// Search for repeat units of length `min_period` to `max_period`, e.g. 7 to 500 for minisatellites
+    pub fn with_periods(mut self, min_period: usize, max_period: usize) -> Self {
+        self.min_period = min_period;
+        self.max_period = max_period;
+        self
+    }
+
+    /// Report repeats of at least `min_copies` copies of their unit
+    pub fn with_min_copies(mut self, min_copies: f64) -> Self {
+        self.min_copies = min_copies;
+        self
+    }
+
+    /// Report repeats of at least `min_length` letters
+    pub fn with_min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length;
+        self
+    }
+
+    /// Fraction of positions within a repeat, which may differ from the letter one period ahead
+    pub fn with_max_mismatch_rate(mut self, max_mismatch_rate: f64) -> Self {
+        self.max_mismatch_rate = max_mismatch_rate;
+        self
+    }
+
+    /// Find all tandem repeats within a sequence, ordered by start and period
+    ///
+    /// Fails with `InvalidArgumentRange` for a period of 0, an empty range of periods or a mismatch rate outside `[0, 1)`.
+    pub fn find(&self, seq: &Sequence) -> Result<Vec<TandemRepeat>> {
+        if self.min_period == 0 || self.min_period > self.max_period
+            || !(0.0..1.0).contains(&self.max_mismatch_rate) {
+            return Err(BioError::InvalidArgumentRange);
+        }
+
+        let text: Vec<u8> = seq.chain.iter().map(|c| c.to_ascii_uppercase()).collect();
+        let n = text.len();
+        let search = Search { text: &text, rate: self.max_mismatch_rate };
+
+        // Accepted repeats of every period, in order of their start
+        let mut accepted: Vec<Vec<TandemRepeat>> = vec![vec![]; self.max_period + 1];
+        for period in self.min_period..=self.max_period {
+            let mut i = 0;
+            while i + period < n {
+                let run = search.forward(i, i + period);
+                if run < period {
+                    i += run + 1;
+                    continue;
+                }
+                let (start, end, mismatches) = search.extend(i, i + period + run, period);
+                i = end - period + 1;
+
+                let length = end - start;
+                if length < self.min_length || (length as f64 / period as f64) < self.min_copies {
+                    continue;
+                }
+                let repeat = tandem_repeat(&text[start..end], start, period, mismatches);
+                if !is_primitive(&repeat.unit) || repeat.unit.chain.iter().any(|&c| encode_base(c).is_none()) {
+                    continue;
+                }
+                let redundant = (1..period)
+                    .filter(|&d| period.is_multiple_of(d))
+                    .any(|d| overlaps_half(&accepted[d], start, end));
+                if !redundant {
+                    accepted[period].push(repeat);
+                }
+            }
+        }
+
+        let mut repeats: Vec<TandemRepeat> = accepted.into_iter().flatten().collect();
+        repeats.sort_by_key(|repeat| (repeat.start, repeat.period));
+        Ok(repeats)
+    }
+}
+
+impl Default for TandemRepeatFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Extension of matches between positions one period apart, comparing the letters directly
+struct Search<'a> {
+    text: &'a [u8],
+    rate: f64,
+}
+
+impl Search<'_> {
+
+    // Number of letters matching from `i` and `j` onwards
+    fn forward(&self, i: usize, j: usize) -> usize {
+        let n = self.text.len();
+        self.text[i.min(n)..].iter().zip(&self.text[j.min(n)..]).take_while(|(a, b)| a == b).count()
+    }
+
+    // Number of letters matching from `i` and `j` backwards
+    fn backward(&self, i: usize, j: usize) -> usize {
+        if i >= self.text.len() || j >= self.text.len() {
+            return 0;
+        }
+        self.text[..=i].iter().rev().zip(self.text[..=j].iter().rev()).take_while(|(a, b)| a == b).count()
+    }
+
+    // Extend an exact periodic stretch `[start, end)` across mismatches, while the mismatch rate is not exceeded.
+    // Stretches end at a mismatch, further mismatches are only accepted if followed by a match.
+    fn extend(&self, mut start: usize, mut end: usize, period: usize) -> (usize, usize, usize) {
+        let mut mismatches = 0;
+        if self.rate == 0.0 {
+            return (start, end, mismatches);
+        }
+
+        let (mut pending, mut pos) = (0, end);
+        while pos < self.text.len() {
+            pending += 1;
+            let run = self.forward(pos - period + 1, pos + 1);
+            pos += 1 + run;
+            if (mismatches + pending) as f64 > self.rate * (pos - period - start) as f64 {
+                break;
+            }
+            if run > 0 {
+                mismatches += pending;
+                pending = 0;
+                end = pos;
+            }
+        }
+
+        let (mut pending, mut pos) = (0, start);
+        while pos > 0 {
+            pending += 1;
+            let run = if pos >= 2 { self.backward(pos - 2, pos - 2 + period) } else { 0 };
+            pos -= 1 + run;
+            if (mismatches + pending) as f64 > self.rate * (end - period - pos) as f64 {
+                break;
+            }
+            if run > 0 {
+                mismatches += pending;
+                pending = 0;
+                start = pos;
+            }
+        }
+        (start, end, mismatches)
+    }
+}
+
+// Determine the consensus unit of a repeat by majority vote over the copies, and the agreement with it
+fn tandem_repeat(region: &[u8], start: usize, period: usize, mismatches: usize) -> TandemRepeat {
+    let mut unit = Sequence::new();
+    let mut agreeing = 0;
+    for column in 0..period {
+        let mut counts = [0_usize; 256];
+        region.iter().skip(column).step_by(period).for_each(|&c| counts[c as usize] += 1);
+        let (letter, count) = counts.iter().enumerate().max_by_key(|&(c, &count)| (count, std::cmp::Reverse(c))).unwrap();
+        unit.push(letter as u8);
+        agreeing += count;
+    }
+    TandemRepeat {
+        start,
+        end: start + region.len(),
+        period,
+        unit,
+        copies: region.len() as f64 / period as f64,
+        purity: agreeing as f64 / region.len() as f64,
+        mismatches,
+    }
+}
+
+// Unit is not a repetition of a shorter unit
+fn is_primitive(unit: &Sequence) -> bool {
+    let p = unit.len();
+    !(1..p).any(|d| p.is_multiple_of(d) && (d..p).all(|i| unit.chain[i] == unit.chain[i - d]))
+}
+
+// Some repeat overlaps at least half of `[start, end)`
+fn overlaps_half(repeats: &[TandemRepeat], start: usize, end: usize) -> bool {
+    let first = repeats.partition_point(|repeat| repeat.end <= start);
+    repeats[first..].iter()
+        .take_while(|repeat| repeat.start < end)
+        .any(|repeat| 2 * (repeat.end.min(end) - repeat.start.max(start)) >= end - start)
+}
@@ -0,0 +1,276 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use crate::ds::sequence::Sequence;
+use crate::ds::tile::Tile;
+use crate::error::{BioError, Result};
+use crate::processing::kmers::{CanonicalKmers, KmerKey};
+use crate::processing::minimizers::hash_kmer;
+
+// Identifies files written by `Sketch::write`
+const MAGIC: &[u8; 8] = b"BGSKTCH1";
+
+/// Rule deciding which k-mer hashes are retained by a `Sketch`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SketchKind {
+    /// Keep the `n` smallest hashes (MinHash), sketch size is independent of the genome size
+    BottomK(usize),
+    /// Keep all hashes below `max_hash / scaled` (FracMinHash), sketch size grows with the genome size
+    FracMinHash(u64),
+}
+
+/// Sketch of the k-mer content of one or more sequences, for alignment-free comparison
+///
+/// Canonical k-mers are hashed with an invertible hash and only a small, deterministic subset of hashes is retained.
+/// Sketches built with the same parameters estimate the Jaccard index (`jaccard`)
+/// and the fraction of k-mers of one sketch contained in another (`containment`) of the underlying k-mer sets.
+/// Bottom-k sketches compare genomes of similar size, FracMinHash sketches
+/// remain accurate for containment of small genomes within large collections.
+///
+/// # Example
+/// ```
+/// use biogarden::analysis::sketch::{Sketch, SketchKind};
+/// use biogarden::ds::sequence::Sequence;
+///
+/// let genome = Sequence::from("ATGCGTACGTTAGCCGATCGATCGGCTAGCTAGGCTTACGATCGATGCATCGTAGCTAGCTGACTGATCG");
+/// let mut mutated = genome.clone();
+/// mutated.chain[35] = b'A';
+///
+/// let s1 = Sketch::from_sequence(&genome, 11, SketchKind::BottomK(100)).unwrap();
+/// let s2 = Sketch::from_sequence(&mutated, 11, SketchKind::BottomK(100)).unwrap();
+///
+/// // Sketches retain all k-mers, 45 out of 67 distinct k-mers are shared
+/// let jaccard = s1.jaccard(&s2).unwrap();
+/// assert!((jaccard - 45.0 / 67.0).abs() < 1e-9);
+/// assert!(s1.mash_distance(&s2).unwrap() > 0.0);
+/// assert_eq!(s1.mash_distance(&s1).unwrap(), 0.0);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sketch {
+    k: usize,
+    kind: SketchKind,
+    // Retained hashes in ascending order
+    hashes: BTreeSet<u64>,
+}
+
+impl Sketch {
+
+    /// Construct an empty sketch of k-mers of length `k` (at most 32)
+    pub fn new(k: usize, kind: SketchKind) -> Result<Sketch> {
+        let valid_kind = match kind {
+            SketchKind::BottomK(n) => n > 0,
+            SketchKind::FracMinHash(scaled) => scaled > 0,
+        };
+        if k == 0 || k > u64::max_k() || !valid_kind {
+            return Err(BioError::InvalidArgumentRange);
+        }
+        Ok(Sketch { k, kind, hashes: BTreeSet::new() })
+    }
+
+    /// Sketch the k-mers of a sequence
+    pub fn from_sequence(seq: &Sequence, k: usize, kind: SketchKind) -> Result<Sketch> {
+        let mut sketch = Sketch::new(k, kind)?;
+        sketch.add_sequence(seq);
+        Ok(sketch)
+    }
+
+    /// Sketch the k-mers of all sequences in a tile, e.g. contigs of an assembly
+    pub fn from_tile(tile: &Tile, k: usize, kind: SketchKind) -> Result<Sketch> {
+        let mut sketch = Sketch::new(k, kind)?;
+        sketch.add_tile(tile);
+        Ok(sketch)
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn kind(&self) -> SketchKind {
+        self.kind
+    }
+
+    /// Retained hashes in ascending order
+    pub fn hashes(&self) -> impl Iterator<Item = &u64> {
+        self.hashes.iter()
+    }
+
+    /// Number of retained hashes
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Add the canonical k-mers of a sequence, k-mers containing letters other than A, C, G, T/U are skipped
+    pub fn add_sequence(&mut self, seq: &Sequence) {
+        let mask = u64::mask(self.k);
+        let threshold = self.threshold();
+        // Length of k-mers has been validated on construction
+        for kmer in CanonicalKmers::<u64>::new(seq, self.k).unwrap().flatten() {
+            let hash = hash_kmer(kmer.canonical(), mask);
+            if hash <= threshold {
+                self.insert(hash);
+            }
+        }
+    }
+
+    /// Add the canonical k-mers of all sequences in a tile
+    pub fn add_tile(&mut self, tile: &Tile) {
+        for seq in tile {
+            self.add_sequence(seq);
+        }
+    }
+
+    /// Combine with a sketch of other sequences, such that the result sketches the union of both k-mer sets
+    pub fn merge(&mut self, other: &Sketch) -> Result<()> {
+        self.check_compatible(other)?;
+        for &hash in &other.hashes {
+            self.insert(hash);
+        }
+        Ok(())
+    }
+
+    /// Estimate the Jaccard index |A ∩ B| / |A ∪ B| of the k-mer sets of two sketches
+    pub fn jaccard(&self, other: &Sketch) -> Result<f64> {
+        self.check_compatible(other)?;
+        let (shared, total) = match self.kind {
+            // Bottom-k sketch of the union: the smallest n hashes of both sketches
+            SketchKind::BottomK(n) => {
+                let union: Vec<&u64> = self.hashes.union(&other.hashes).take(n).collect();
+                let shared = union.iter().filter(|h| self.hashes.contains(h) && other.hashes.contains(h)).count();
+                (shared, union.len())
+            }
+            SketchKind::FracMinHash(_) => {
+                (self.hashes.intersection(&other.hashes).count(), self.hashes.union(&other.hashes).count())
+            }
+        };
+        Ok(if total == 0 { 0.0 } else { shared as f64 / total as f64 })
+    }
+
+    /// Estimate the containment |A ∩ B| / |A| of the k-mers of this sketch (A) within another sketch (B)
+    ///
+    /// For bottom-k sketches, only hashes within the range covered by both sketches are compared.
+    pub fn containment(&self, other: &Sketch) -> Result<f64> {
+        self.check_compatible(other)?;
+        let limit = match self.kind {
+            SketchKind::BottomK(_) => std::cmp::min(self.max_hash(), other.max_hash()),
+            SketchKind::FracMinHash(_) => u64::MAX,
+        };
+        let own: Vec<&u64> = self.hashes.range(..=limit).collect();
+        let shared = own.iter().filter(|h| other.hashes.contains(h)).count();
+        Ok(if own.is_empty() { 0.0 } else { shared as f64 / own.len() as f64 })
+    }
+
+    /// Mash distance, an estimate of the per-base mutation rate between the sketched sequences
+    ///
+    /// Derived from the Jaccard index `j` as `-1/k * ln(2j / (1 + j))`, sequences without shared k-mers have a distance of 1.
+    pub fn mash_distance(&self, other: &Sketch) -> Result<f64> {
+        let jaccard = self.jaccard(other)?;
+        if jaccard == 0.0 {
+            return Ok(1.0);
+        }
+        let distance = -1.0 / self.k as f64 * (2.0 * jaccard / (1.0 + jaccard)).ln();
+        Ok(distance.clamp(0.0, 1.0))
+    }
+
+    /// Serialise the sketch into a writer
+    pub fn write<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(writer);
+        w.write_all(MAGIC)?;
+        let (tag, parameter) = match self.kind {
+            SketchKind::BottomK(n) => (0, n as u64),
+            SketchKind::FracMinHash(scaled) => (1, scaled),
+        };
+        for x in [self.k as u64, tag, parameter, self.hashes.len() as u64].iter().chain(self.hashes.iter()) {
+            w.write_all(&x.to_le_bytes())?;
+        }
+        w.flush()
+    }
+
+    /// Deserialise a sketch written by `Sketch::write`
+    ///
+    /// Fails with `InvalidData` for sketches retaining more than `n` hashes for `BottomK(n)`,
+    /// or hashes above the threshold for `FracMinHash`.
+    pub fn read<R: io::Read>(reader: R) -> io::Result<Sketch> {
+        let mut r = io::BufReader::new(reader);
+        let mut magic = [0_u8; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a sketch file."));
+        }
+        let k = read_u64(&mut r)? as usize;
+        let kind = match (read_u64(&mut r)?, read_u64(&mut r)?) {
+            (0, n) => SketchKind::BottomK(n as usize),
+            (1, scaled) => SketchKind::FracMinHash(scaled),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "Unknown sketch kind.")),
+        };
+        let len = read_u64(&mut r)?;
+        let hashes = (0..len).map(|_| read_u64(&mut r)).collect::<io::Result<BTreeSet<u64>>>()?;
+        let mut sketch = Sketch::new(k, kind)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid sketch parameters."))?;
+        let too_many = matches!(kind, SketchKind::BottomK(n) if hashes.len() > n);
+        if too_many || hashes.last().is_some_and(|&hash| hash > sketch.threshold()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Inconsistent sketch file."));
+        }
+        sketch.hashes = hashes;
+        Ok(sketch)
+    }
+
+    /// Write the sketch to the given file path
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write(fs::File::create(path)?)
+    }
+
+    /// Read the sketch from the given file path
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Sketch> {
+        Sketch::read(fs::File::open(path)?)
+    }
+
+    // Largest hash admitted by FracMinHash, all hashes are candidates for bottom-k
+    fn threshold(&self) -> u64 {
+        match self.kind {
+            SketchKind::BottomK(_) => u64::MAX,
+            SketchKind::FracMinHash(scaled) => u64::mask(self.k) / scaled,
+        }
+    }
+
+    // Largest retained hash, or the whole hash range if the sketch is not saturated
+    fn max_hash(&self) -> u64 {
+        match self.kind {
+            SketchKind::BottomK(n) if self.hashes.len() >= n => *self.hashes.iter().next_back().unwrap(),
+            _ => u64::MAX,
+        }
+    }
+
+    fn insert(&mut self, hash: u64) {
+        if let SketchKind::BottomK(n) = self.kind {
+            if self.hashes.len() >= n && hash >= self.max_hash() {
+                return;
+            }
+            self.hashes.insert(hash);
+            if self.hashes.len() > n {
+                self.hashes.pop_last();
+            }
+        } else {
+            self.hashes.insert(hash);
+        }
+    }
+
+    fn check_compatible(&self, other: &Sketch) -> Result<()> {
+        if self.k != other.k || self.kind != other.kind {
+            return Err(BioError::InvalidArgumentRange);
+        }
+        Ok(())
+    }
+}
+
+fn read_u64<R: io::Read>(r: &mut R) -> io::Result<u64> {
+    let mut buf = [0_u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
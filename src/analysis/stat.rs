@@ -99,6 +99,58 @@ pub fn n_statistic(tile: &Tile, xx: usize) -> usize {
     nxx
 }
 
+/// Summary of the contiguity of an assembly, see `assembly_statistics`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssemblyStatistics {
+    /// Number of contigs
+    pub contigs: usize,
+    /// Sum of contig lengths
+    pub total_length: usize,
+    /// Length of the longest contig
+    pub largest_contig: usize,
+    /// N50, half of the assembly is contained in contigs at least this long
+    pub n50: usize,
+    /// N75, three quarters of the assembly are contained in contigs at least this long
+    pub n75: usize,
+    /// N90, 90% of the assembly is contained in contigs at least this long
+    pub n90: usize,
+}
+
+/// Summarize the contiguity of an assembly, based on the `NXX`-statistic (see `n_statistic`)
+///
+/// # Arguments
+/// * `contigs` - assembled contigs, e.g. obtained with `ds::algo::assembly::Assembler`
+///
+/// # Example
+/// ```
+/// use biogarden::analysis::stat::assembly_statistics;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// let mut contigs = Tile::new();
+/// contigs.push(Sequence::from("GATTACA"));
+/// contigs.push(Sequence::from("TACTACTAC"));
+/// contigs.push(Sequence::from("ATTGAT"));
+/// contigs.push(Sequence::from("GAAGA"));
+///
+/// let stats = assembly_statistics(&contigs);
+/// assert_eq!((stats.contigs, stats.total_length, stats.largest_contig), (4, 27, 9));
+/// assert_eq!((stats.n50, stats.n75, stats.n90), (7, 6, 5));
+/// ```
+pub fn assembly_statistics(contigs: &Tile) -> AssemblyStatistics {
+    let total_length = contigs.into_iter().map(|seq| seq.len()).sum::<usize>();
+    // NXX is undefined for an empty assembly
+    let nxx = |xx| if total_length == 0 { 0 } else { n_statistic(contigs, xx) };
+    AssemblyStatistics {
+        contigs: contigs.len(),
+        total_length,
+        largest_contig: contigs.into_iter().map(|seq| seq.len()).max().unwrap_or(0),
+        n50: nxx(50),
+        n75: nxx(75),
+        n90: nxx(90),
+    }
+}
+
 pub fn permutations<T: Clone>(n: usize, a: &mut Vec<T>, result: &mut Vec<Vec<T>>) {
     if n == 1 {
         result.push(a.clone());
@@ -0,0 +1,376 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use crate::ds::builders::debruijn::DeBruijnBuilder;
+use crate::ds::graph::Graph;
+use crate::ds::sequence::Sequence;
+use crate::ds::tile::Tile;
+use crate::error::{BioError, Result};
+use crate::processing::transformers::reverse_complement;
+
+/// Maximal non-branching path of a De Bruijn graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unitig {
+    /// Nodes ((k-1)-mers) along the path, first and last node coincide for isolated cycles
+    pub nodes: Vec<u64>,
+    /// Edges (k-mers) along the path
+    pub edges: Vec<u64>,
+    /// Sequence spelled by the path
+    pub sequence: Sequence,
+    /// Mean count of the k-mers along the path
+    pub coverage: f64,
+}
+
+/// Compact a De Bruijn graph into unitigs, the maximal paths whose inner nodes have a single predecessor and successor
+///
+/// Every edge of the graph belongs to exactly one unitig. Unitigs start and end at branching nodes,
+/// sources or sinks, except for isolated cycles which are opened at an arbitrary node.
+///
+/// # Arguments
+/// * `graph` - De Bruijn graph as constructed by `DeBruijnBuilder`, edges labelled with k-mer counts
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::assembly::unitigs;
+/// use biogarden::ds::builders::debruijn::DeBruijnBuilder;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// // Repeat CAT splits the reads into three unitigs
+/// let mut reads = Tile::new();
+/// reads.push(Sequence::from("GGCATAACATTT"));
+/// let graph = DeBruijnBuilder::new().with_k(4).with_counts(true).build(&reads);
+///
+/// let mut unitigs: Vec<Sequence> = unitigs(&graph).into_iter().map(|u| u.sequence).collect();
+/// unitigs.sort_by(|a, b| a.chain.cmp(&b.chain));
+/// assert_eq!(unitigs, vec![Sequence::from("CATAACAT"), Sequence::from("CATTT"), Sequence::from("GGCAT")]);
+/// ```
+pub fn unitigs(graph: &Graph<Sequence, usize>) -> Vec<Unitig> {
+    let mut unitigs = Vec::<Unitig>::new();
+    let mut visited = HashSet::<u64>::new();
+
+    // Visit nodes in order of their ids, for reproducible results
+    let mut nodes: Vec<u64> = graph.nodes().cloned().collect();
+    nodes.sort_unstable();
+
+    // Paths starting at branching nodes, sources and sinks
+    for &node in nodes.iter().filter(|&&node| !is_non_branching(graph, node)) {
+        for &edge in graph.get_node(&node).outgoing() {
+            unitigs.push(extend_unitig(graph, node, edge, &mut visited));
+        }
+    }
+    // Remaining edges form isolated cycles
+    for &node in &nodes {
+        for &edge in graph.get_node(&node).outgoing() {
+            if !visited.contains(&edge) {
+                unitigs.push(extend_unitig(graph, node, edge, &mut visited));
+            }
+        }
+    }
+    unitigs
+}
+
+/// Remove tips, short dead-end paths caused by sequencing errors close to the end of reads
+///
+/// A tip is a unitig of at most `max_len` k-mers which starts at a source and joins another path,
+/// or branches off another path and ends in a sink. If all paths entering or leaving a node are tips,
+/// the best supported one is kept, as it likely represents the end of the genome.
+/// Returns the number of removed tips.
+///
+/// # Arguments
+/// * `graph` - De Bruijn graph as constructed by `DeBruijnBuilder`, edges labelled with k-mer counts
+/// * `max_len` - maximum number of k-mers of a tip, typically a small multiple of `k`
+pub fn clip_tips(graph: &mut Graph<Sequence, usize>, max_len: usize) -> usize {
+    // Group tips by the node they are attached to and the side they are attached on
+    let mut groups = HashMap::<(u64, bool), Vec<Unitig>>::new();
+    for unitig in unitigs(graph) {
+        let (first, last) = (unitig.nodes[0], *unitig.nodes.last().unwrap());
+        if unitig.edges.len() > max_len || first == last {
+            continue;
+        }
+        if in_degree(graph, first) == 0 && in_degree(graph, last) > 1 {
+            groups.entry((last, true)).or_default().push(unitig);
+        } else if out_degree(graph, last) == 0 && out_degree(graph, first) > 1 {
+            groups.entry((first, false)).or_default().push(unitig);
+        }
+    }
+
+    let mut removed = 0_usize;
+    for ((anchor, entering), mut tips) in groups {
+        let degree = if entering { in_degree(graph, anchor) } else { out_degree(graph, anchor) };
+        tips.sort_by(|a, b| compare_support(b, a));
+        let keep = if tips.len() == degree { 1 } else { 0 };
+        for tip in &tips[keep..] {
+            remove_unitig(graph, tip);
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Pop bubbles, short alternative paths between the same nodes caused by sequencing errors or polymorphisms
+///
+/// For every unitig of at most `max_len` k-mers, the shortest alternative path between its first and last node is searched.
+/// If such a path of at most `max_len` k-mers exists and is better supported, the unitig is removed.
+/// Unitigs are examined in order of increasing coverage. Returns the number of removed paths.
+///
+/// # Arguments
+/// * `graph` - De Bruijn graph as constructed by `DeBruijnBuilder`, edges labelled with k-mer counts
+/// * `max_len` - maximum number of k-mers of a bubble path, typically a small multiple of `k`
+pub fn pop_bubbles(graph: &mut Graph<Sequence, usize>, max_len: usize) -> usize {
+    let mut candidates: Vec<Unitig> = unitigs(graph)
+        .into_iter()
+        .filter(|u| u.edges.len() <= max_len && u.nodes[0] != *u.nodes.last().unwrap())
+        .collect();
+    candidates.sort_by(compare_support);
+
+    let mut removed = 0_usize;
+    for unitig in &candidates {
+        // Skip paths affected by previous removals
+        if !unitig.edges.iter().all(|e| graph.contains_edge(e)) {
+            continue;
+        }
+        let alternative = match alternative_path(graph, unitig, max_len) {
+            Some(alternative) => alternative,
+            None => continue,
+        };
+        if compare_support(unitig, &alternative) == Ordering::Less {
+            remove_unitig(graph, unitig);
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Spell the unitigs of a De Bruijn graph as contigs, sorted by decreasing length
+///
+/// If the graph represents both strands, only one of each unitig and its reverse complement is reported.
+/// Contigs are labelled `contig_1`, `contig_2`, ...
+pub fn contigs(graph: &Graph<Sequence, usize>, both_strands: bool) -> Tile {
+    let mut sequences: Vec<Sequence> = unitigs(graph).into_iter().map(|unitig| unitig.sequence).collect();
+    sequences.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.chain.cmp(&b.chain)));
+
+    let mut reported = HashSet::<Sequence>::new();
+    let mut contigs = Tile::new();
+    for mut seq in sequences {
+        if both_strands && reported.contains(&reverse_complement(&seq)) {
+            continue;
+        }
+        reported.insert(seq.clone());
+        seq.id = Some(format!("contig_{}", contigs.len() + 1));
+        contigs.push(seq);
+    }
+    contigs
+}
+
+/// Assemble reads into contigs with a De Bruijn graph
+///
+/// Reads are split into k-mers, which are connected by their (k-1)-mer overlaps into a De Bruijn graph.
+/// The graph is cleaned from sequencing errors by repeatedly clipping tips and popping bubbles until no more are found,
+/// and its unitigs are reported as contigs. The quality of the assembly can be assessed with `analysis::stat::assembly_statistics`.
+///
+/// Defaults: both strands, all k-mers retained, tips and bubbles of up to `3k` k-mers removed, no minimum contig length.
+/// Reads with errors in all of their k-mers form separate short contigs, which are removed by `with_min_count(2)` given sufficient coverage.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::assembly::Assembler;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+/// use biogarden::processing::transformers::reverse_complement;
+///
+/// let genome = Sequence::from("GCTAAAGACAATTACATAACATACACGTCAGCACGAAACTTGTTGGCCCAGTGTGAATCG");
+///
+/// // Overlapping reads from both strands, one of them with a sequencing error
+/// let mut reads = Tile::new();
+/// for start in (0..=genome.len() - 20).step_by(2) {
+///     let read = Sequence::from(&genome[start..start + 20]);
+///     reads.push(if start % 4 == 0 { read } else { reverse_complement(&read) });
+/// }
+/// reads.push(Sequence::from("GCTAAAGACAATTCCATAAC"));
+///
+/// let contigs = Assembler::new(11).unwrap().assemble(&reads);
+/// assert_eq!(contigs.len(), 1);
+/// assert!(contigs[0] == genome || contigs[0] == reverse_complement(&genome));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Assembler {
+    k: usize,
+    min_count: usize,
+    both_strands: bool,
+    max_tip_len: usize,
+    max_bubble_len: usize,
+    min_contig_len: usize,
+}
+
+impl Assembler {
+
+    /// Assembler using k-mers of length `k`, which should exceed the length of short repeats while being smaller than reads
+    pub fn new(k: usize) -> Result<Assembler> {
+        if k < 2 {
+            return Err(BioError::InvalidArgumentRange);
+        }
+        Ok(Assembler {
+            k,
+            min_count: 1,
+            both_strands: true,
+            max_tip_len: 3 * k,
+            max_bubble_len: 3 * k,
+            min_contig_len: 0,
+        })
+    }
+
+    /// Discard k-mers occurring less than `min_count` times before constructing the graph
+    pub fn with_min_count(mut self, min_count: usize) -> Self {
+        self.min_count = min_count;
+        self
+    }
+
+    /// Assemble reads together with their reverse complements, for reads sequenced from both strands
+    pub fn with_both_strands(mut self, both_strands: bool) -> Self {
+        self.both_strands = both_strands;
+        self
+    }
+
+    /// Maximum number of k-mers of removed tips, see `clip_tips`
+    pub fn with_max_tip_len(mut self, max_tip_len: usize) -> Self {
+        self.max_tip_len = max_tip_len;
+        self
+    }
+
+    /// Maximum number of k-mers of removed bubble paths, see `pop_bubbles`
+    pub fn with_max_bubble_len(mut self, max_bubble_len: usize) -> Self {
+        self.max_bubble_len = max_bubble_len;
+        self
+    }
+
+    /// Discard contigs shorter than `min_contig_len`
+    pub fn with_min_contig_len(mut self, min_contig_len: usize) -> Self {
+        self.min_contig_len = min_contig_len;
+        self
+    }
+
+    /// Construct the De Bruijn graph of the reads, before error removal
+    pub fn graph(&self, reads: &Tile) -> Graph<Sequence, usize> {
+        DeBruijnBuilder::new()
+            .with_k(self.k)
+            .with_counts(true)
+            .with_min_count(self.min_count)
+            .with_nucleotides_only(true)
+            .with_both_strands(self.both_strands)
+            .build(reads)
+    }
+
+    /// Assemble reads into contigs
+    pub fn assemble(&self, reads: &Tile) -> Tile {
+        let mut graph = self.graph(reads);
+        // Removing errors merges unitigs, which may reveal further tips and bubbles
+        while clip_tips(&mut graph, self.max_tip_len) + pop_bubbles(&mut graph, self.max_bubble_len) > 0 {}
+
+        let mut assembly = Tile::new();
+        for seq in contigs(&graph, self.both_strands) {
+            if seq.len() >= self.min_contig_len {
+                assembly.push(seq);
+            }
+        }
+        assembly
+    }
+}
+
+// Walk from `start` along `edge` until reaching a branching node, sink, source or the start again
+fn extend_unitig(graph: &Graph<Sequence, usize>, start: u64, edge: u64, visited: &mut HashSet<u64>) -> Unitig {
+    let mut edges = Vec::<u64>::new();
+    let mut edge = edge;
+    loop {
+        visited.insert(edge);
+        edges.push(edge);
+        let next = graph.get_edge(&edge).end;
+        if next == start || !is_non_branching(graph, next) {
+            break;
+        }
+        edge = graph.get_node(&next).outgoing()[0];
+    }
+    spell_path(graph, edges)
+}
+
+// Shortest path from the first to the last node of a unitig avoiding its first edge, limited to `max_len` edges
+fn alternative_path(graph: &Graph<Sequence, usize>, unitig: &Unitig, max_len: usize) -> Option<Unitig> {
+    let (source, target) = (unitig.nodes[0], *unitig.nodes.last().unwrap());
+    let mut parent = HashMap::<u64, u64>::new();
+    let mut frontier = vec![source];
+    for _ in 0..max_len {
+        let mut next_frontier = Vec::<u64>::new();
+        for node in frontier {
+            for &edge in graph.get_node(&node).outgoing() {
+                let next = graph.get_edge(&edge).end;
+                if edge == unitig.edges[0] || next == source || parent.contains_key(&next) {
+                    continue;
+                }
+                parent.insert(next, edge);
+                next_frontier.push(next);
+            }
+        }
+        if parent.contains_key(&target) {
+            break;
+        }
+        frontier = next_frontier;
+    }
+
+    // Trace path back from the target
+    let mut edges = vec![*parent.get(&target)?];
+    while graph.get_edge(edges.last().unwrap()).start != source {
+        edges.push(parent[&graph.get_edge(edges.last().unwrap()).start]);
+    }
+    edges.reverse();
+    Some(spell_path(graph, edges))
+}
+
+// Spell the sequence along consecutive edges
+fn spell_path(graph: &Graph<Sequence, usize>, edges: Vec<u64>) -> Unitig {
+    let start = graph.get_edge(&edges[0]).start;
+    let mut nodes = vec![start];
+    nodes.extend(edges.iter().map(|e| graph.get_edge(e).end));
+
+    // Consecutive nodes overlap by all but their last letter
+    let mut sequence = graph.get_node(&start).data.clone();
+    sequence.id = None;
+    for node in &nodes[1..] {
+        sequence.chain.push(*graph.get_node(node).data.chain.last().unwrap());
+    }
+    let total: usize = edges.iter().map(|e| graph.get_edge(e).data.unwrap_or(1)).sum();
+    let coverage = total as f64 / edges.len() as f64;
+
+    Unitig { nodes, edges, sequence, coverage }
+}
+
+// Remove edges of a unitig, together with nodes left without any edges
+fn remove_unitig(graph: &mut Graph<Sequence, usize>, unitig: &Unitig) {
+    for edge in &unitig.edges {
+        graph.remove_edge(edge);
+    }
+    for node in &unitig.nodes {
+        if graph.node_degree(node) == Some(0) {
+            graph.remove_node(*node);
+        }
+    }
+}
+
+// Order paths by coverage and length, ties broken by sequence independent of the strand
+fn compare_support(a: &Unitig, b: &Unitig) -> Ordering {
+    let canonical = |u: &Unitig| std::cmp::min(u.sequence.chain.clone(), reverse_complement(&u.sequence).chain);
+    a.coverage.partial_cmp(&b.coverage).unwrap_or(Ordering::Equal)
+        .then_with(|| a.edges.len().cmp(&b.edges.len()))
+        .then_with(|| canonical(b).cmp(&canonical(a)))
+}
+
+fn in_degree(graph: &Graph<Sequence, usize>, node: u64) -> usize {
+    graph.get_node(&node).incoming().len()
+}
+
+fn out_degree(graph: &Graph<Sequence, usize>, node: u64) -> usize {
+    graph.get_node(&node).outgoing().len()
+}
+
+fn is_non_branching(graph: &Graph<Sequence, usize>, node: u64) -> bool {
+    in_degree(graph, node) == 1 && out_degree(graph, node) == 1
+}
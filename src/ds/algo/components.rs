@@ -0,0 +1,235 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::ds::graph::{Graph, GraphProperties, NodeId};
+
+/// Find the strongly connected components of a directed graph, using Tarjan's algorithm
+///
+/// Complexity: O(V + E)
+///
+/// Two nodes belong to the same strongly connected component, if each of them can be reached from the other.
+/// Components are returned in topological order of the condensation, i.e. edges between components
+/// only lead from earlier to later components. Nodes within a component are sorted by their id.
+/// The search is iterative, such that large graphs do not overflow the stack.
+///
+/// # Arguments
+/// * `graph` - directed graph
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::components::strongly_connected_components;
+/// use biogarden::ds::graph::{Graph, GraphProperties, NodeId};
+///
+/// let mut graph = Graph::<char, u8>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// let d = graph.add_node('d');
+/// graph.add_edge(&a, &b, None).unwrap();
+/// graph.add_edge(&b, &a, None).unwrap();
+/// graph.add_edge(&b, &c, None).unwrap();
+/// graph.add_edge(&d, &c, None).unwrap();
+///
+/// let [a, b, c, d] = [a, b, c, d].map(NodeId);
+/// assert_eq!(strongly_connected_components(&graph), vec![vec![d], vec![a, b], vec![c]]);
+/// ```
+pub fn strongly_connected_components<N, E>(graph: &Graph<N, E>) -> Vec<Vec<NodeId>>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone
+{
+    const UNVISITED: usize = usize::MAX;
+
+    // Discovery index and lowest index reachable through the DFS subtree of every node
+    let mut index = vec![UNVISITED; graph.node_bound()];
+    let mut low = vec![UNVISITED; graph.node_bound()];
+    let mut on_stack = vec![false; graph.node_bound()];
+    let mut stack = Vec::<u64>::new();
+    let mut counter = 0;
+    let mut components = Vec::<Vec<NodeId>>::new();
+
+    // Nodes of the simulated recursion, with the position of the next outgoing edge to follow
+    let mut calls = Vec::<(u64, usize)>::new();
+
+    for &root in graph.nodes() {
+        if index[root as usize] != UNVISITED {
+            continue;
+        }
+        calls.push((root, 0));
+
+        while let Some(&(node, next)) = calls.last() {
+            let v = node as usize;
+            if next == 0 && index[v] == UNVISITED {
+                index[v] = counter;
+                low[v] = counter;
+                counter += 1;
+                stack.push(node);
+                on_stack[v] = true;
+            }
+
+            let outgoing = graph.get_node(&node).outgoing();
+            if next < outgoing.len() {
+                calls.last_mut().unwrap().1 += 1;
+                let w = graph.get_edge(&outgoing[next]).end;
+                if index[w as usize] == UNVISITED {
+                    calls.push((w, 0));
+                } else if on_stack[w as usize] {
+                    low[v] = low[v].min(index[w as usize]);
+                }
+                continue;
+            }
+
+            // All successors processed, propagate the lowest index to the parent
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent as usize] = low[parent as usize].min(low[v]);
+            }
+
+            // Node is the root of a component, consisting of the nodes above it on the stack
+            if low[v] == index[v] {
+                let mut component = Vec::<NodeId>::new();
+                while let Some(w) = stack.pop() {
+                    on_stack[w as usize] = false;
+                    component.push(NodeId(w));
+                    if w == node {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+
+    // Tarjan's algorithm completes components in reverse topological order
+    components.reverse();
+    components
+}
+
+/// Find the weakly connected components of a graph, in which nodes are connected regardless of the edge direction
+///
+/// Complexity: O(V + E)
+///
+/// Components are ordered by their smallest node id, nodes within a component are sorted by their id.
+/// Weakly connected components of an assembly graph can be processed independently.
+///
+/// # Arguments
+/// * `graph` - directed or undirected graph
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::components::weakly_connected_components;
+/// use biogarden::ds::graph::{Graph, GraphProperties, NodeId};
+///
+/// let mut graph = Graph::<char, u8>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// let d = graph.add_node('d');
+/// graph.add_edge(&a, &c, None).unwrap();
+/// graph.add_edge(&d, &c, None).unwrap();
+///
+/// let [a, b, c, d] = [a, b, c, d].map(NodeId);
+/// assert_eq!(weakly_connected_components(&graph), vec![vec![a, c, d], vec![b]]);
+/// ```
+pub fn weakly_connected_components<N, E>(graph: &Graph<N, E>) -> Vec<Vec<NodeId>>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone
+{
+    // Union-find over the node ids, merging the nodes of every edge
+    let mut parent: Vec<usize> = (0..graph.node_bound()).collect();
+    fn find(parent: &mut [usize], x: usize) -> usize {
+        let mut root = x;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        // Path compression
+        let mut x = x;
+        while parent[x] != root {
+            let next = parent[x];
+            parent[x] = root;
+            x = next;
+        }
+        root
+    }
+    for &edge in graph.edges() {
+        let edge = graph.get_edge(&edge);
+        let (a, b) = (find(&mut parent, edge.start as usize), find(&mut parent, edge.end as usize));
+        // Keep the smaller id as representative
+        parent[a.max(b)] = a.min(b);
+    }
+
+    let mut components = BTreeMap::<usize, Vec<NodeId>>::new();
+    for node in graph.node_ids() {
+        let root = find(&mut parent, node.index());
+        components.entry(root).or_default().push(node);
+    }
+    components.into_values().collect()
+}
+
+/// Graph of strongly connected components, see `condensation`
+#[derive(Debug, Clone)]
+pub struct Condensation {
+    /// Directed acyclic graph with a node for every component, labelled with the component index,
+    /// and an edge between components connected by edges, labelled with the number of these edges
+    pub graph: Graph<usize, usize>,
+    /// Nodes of the components in topological order, the node of the i-th component in `graph` has id `i`
+    pub components: Vec<Vec<NodeId>>,
+    /// Component index of every node
+    pub component_of: HashMap<NodeId, usize>,
+}
+
+/// Contract every strongly connected component of a graph into a single node
+///
+/// Complexity: O(V + E)
+///
+/// The resulting condensation is a directed acyclic graph, e.g. suitable for `dag::topological_sort`.
+///
+/// # Arguments
+/// * `graph` - directed graph
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::components::condensation;
+/// use biogarden::ds::graph::{Graph, GraphProperties, NodeId};
+///
+/// let mut graph = Graph::<char, u8>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// graph.add_edge(&a, &b, None).unwrap();
+/// graph.add_edge(&b, &a, None).unwrap();
+/// graph.add_edge(&a, &c, None).unwrap();
+/// graph.add_edge(&b, &c, None).unwrap();
+///
+/// let dag = condensation(&graph);
+/// assert_eq!(dag.components, vec![vec![NodeId(a), NodeId(b)], vec![NodeId(c)]]);
+/// assert_eq!(dag.component_of[&NodeId(c)], 1);
+/// assert_eq!((dag.graph.node_count(), dag.graph.edge_count()), (2, 1));
+/// // Both edges leaving the cycle are merged
+/// let edge = dag.graph.has_edge(&0, &1).unwrap();
+/// assert_eq!(dag.graph.get_edge(&edge).data, Some(2));
+/// ```
+pub fn condensation<N, E>(graph: &Graph<N, E>) -> Condensation
+    where N: fmt::Display + Clone, E: fmt::Display + Clone
+{
+    let components = strongly_connected_components(graph);
+    let mut component_of = HashMap::<NodeId, usize>::new();
+    let mut dag = Graph::<usize, usize>::new(GraphProperties{directed: true});
+    for (i, component) in components.iter().enumerate() {
+        dag.add_node(i);
+        component_of.extend(component.iter().map(|&node| (node, i)));
+    }
+
+    // Count the edges between every pair of components, ignoring edges within components
+    let mut counts = BTreeMap::<(usize, usize), usize>::new();
+    for &edge in graph.edges() {
+        let edge = graph.get_edge(&edge);
+        let (a, b) = (component_of[&NodeId(edge.start)], component_of[&NodeId(edge.end)]);
+        if a != b {
+            *counts.entry((a, b)).or_insert(0) += 1;
+        }
+    }
+    for ((a, b), count) in counts {
+        dag.add_edge(&(a as u64), &(b as u64), Some(count)).unwrap();
+    }
+
+    Condensation { graph: dag, components, component_of }
+}
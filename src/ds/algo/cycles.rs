@@ -85,6 +85,7 @@ pub fn cycles<N, E>(graph: &mut Graph<N, E>) -> Vec<Vec::<u64>>
     cycles
 }
 
+// Enumerate all Eulerian circuits starting at `source`, see `euler::eulerian_path` to construct a single path efficiently
 // TODO: Move eulerian circuit detection as member of graph
 pub fn eulerian_circuit<N, E>(graph: &mut Graph<N, E>, source: u64) -> Vec<Vec<u64> >
             where N: fmt::Display + Clone , E: fmt::Display + Clone
@@ -0,0 +1,223 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::Add;
+
+use crate::ds::graph::{Edge, EdgeId, Graph, GraphErr, NodeId};
+
+/// Order the nodes of a directed acyclic graph, such that every edge leads from an earlier to a later node
+///
+/// Complexity: O(V + E)
+///
+/// Uses Kahn's algorithm, repeatedly removing nodes without incoming edges, which are taken in order of their ids.
+/// Fails with `CycleDetected`, if the graph contains a cycle.
+///
+/// # Arguments
+/// * `graph` - directed acyclic graph
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::dag::topological_sort;
+/// use biogarden::ds::graph::{Graph, GraphErr, GraphProperties, NodeId};
+///
+/// let mut graph = Graph::<char, u8>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// graph.add_edge(&c, &a, None).unwrap();
+/// graph.add_edge(&a, &b, None).unwrap();
+///
+/// assert_eq!(topological_sort(&graph), Ok(vec![NodeId(c), NodeId(a), NodeId(b)]));
+///
+/// graph.add_edge(&b, &c, None).unwrap();
+/// assert_eq!(topological_sort(&graph), Err(GraphErr::CycleDetected));
+/// ```
+pub fn topological_sort<N, E>(graph: &Graph<N, E>) -> Result<Vec<NodeId>, GraphErr>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone
+{
+    // Number of incoming edges of every node, which have not been processed yet
+    let mut in_degree = vec![0; graph.node_bound()];
+    let mut queue = VecDeque::<u64>::new();
+    for &node in graph.nodes() {
+        in_degree[node as usize] = graph.get_node(&node).incoming().len();
+        if in_degree[node as usize] == 0 {
+            queue.push_back(node);
+        }
+    }
+
+    let mut order = Vec::<NodeId>::with_capacity(graph.node_count());
+    while let Some(node) = queue.pop_front() {
+        order.push(NodeId(node));
+        for &next in graph.out_neighbors(node) {
+            in_degree[next as usize] -= 1;
+            if in_degree[next as usize] == 0 {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    // Nodes on a cycle never lose all of their incoming edges
+    if order.len() < graph.node_count() {
+        return Err(GraphErr::CycleDetected);
+    }
+    Ok(order)
+}
+
+/// Optimal path within a directed acyclic graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct DagPath<W> {
+    /// Nodes along the path
+    pub nodes: Vec<NodeId>,
+    /// Edges along the path
+    pub edges: Vec<EdgeId>,
+    /// Total weight of the edges along the path
+    pub weight: W,
+}
+
+/// Find the path of maximum weight within a directed acyclic graph
+///
+/// Complexity: O(V + E)
+///
+/// Edges are weighted by a closure, e.g. `|e| e.data.unwrap()`, weights may be negative.
+/// The path may be restricted to start at `source` and to end at `target`, otherwise it starts or ends at any node.
+/// Returns `None`, if `target` cannot be reached from `source`. Fails with `CycleDetected`, if the graph contains a cycle,
+/// and with `NoSuchVertex`, if `source` or `target` do not exist.
+///
+/// Applications include alignment graphs (e.g. the Manhattan tourist problem), where the optimal alignment
+/// corresponds to the longest path from the source to the sink, and the heaviest path through partial order alignments.
+///
+/// # Arguments
+/// * `graph` - directed acyclic graph
+/// * `source` - first node of the path, any node if `None`
+/// * `target` - last node of the path, any node if `None`
+/// * `weight` - weight of an edge
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::dag::dag_longest_path;
+/// use biogarden::ds::graph::{Graph, GraphProperties, NodeId};
+///
+/// let mut graph = Graph::<usize, i32>::new(GraphProperties{directed: true});
+/// let nodes: Vec<NodeId> = (0..5).map(|i| NodeId(graph.add_node(i))).collect();
+/// for (a, b, w) in [(0, 1, 7), (0, 2, 4), (2, 3, 2), (1, 4, 1), (3, 4, 3)] {
+///     graph.add_edge(&nodes[a].into(), &nodes[b].into(), Some(w)).unwrap();
+/// }
+///
+/// let path = dag_longest_path(&graph, Some(nodes[0]), Some(nodes[4]), |e| e.data.unwrap()).unwrap().unwrap();
+/// assert_eq!(path.weight, 9);
+/// assert_eq!(path.nodes, vec![nodes[0], nodes[2], nodes[3], nodes[4]]);
+///
+/// // Without restricting the end of the path
+/// let path = dag_longest_path(&graph, Some(nodes[0]), None, |e| e.data.unwrap()).unwrap().unwrap();
+/// assert_eq!(path.nodes, vec![nodes[0], nodes[2], nodes[3], nodes[4]]);
+/// ```
+pub fn dag_longest_path<N, E, W, F>(graph: &Graph<N, E>, source: Option<NodeId>, target: Option<NodeId>, weight: F)
+                                    -> Result<Option<DagPath<W>>, GraphErr>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone,
+          W: Copy + PartialOrd + Add<Output = W> + Default, F: Fn(&Edge<E>) -> W
+{
+    dag_optimal_path(graph, source, target, weight, |a, b| a > b)
+}
+
+/// Find the path of minimum weight within a directed acyclic graph
+///
+/// Complexity: O(V + E)
+///
+/// Counterpart of `dag_longest_path`, unlike Dijkstra's algorithm negative weights are supported.
+///
+/// # Arguments
+/// * `graph` - directed acyclic graph
+/// * `source` - first node of the path, any node if `None`
+/// * `target` - last node of the path, any node if `None`
+/// * `weight` - weight of an edge
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::dag::dag_shortest_path;
+/// use biogarden::ds::graph::{Graph, GraphProperties, NodeId};
+///
+/// let mut graph = Graph::<char, i32>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// graph.add_edge(&a, &b, Some(5)).unwrap();
+/// graph.add_edge(&b, &c, Some(-4)).unwrap();
+/// let ac = graph.add_edge(&a, &c, Some(2)).unwrap();
+///
+/// let path = dag_shortest_path(&graph, Some(a.into()), Some(c.into()), |e| e.data.unwrap()).unwrap().unwrap();
+/// assert_eq!((path.weight, path.nodes), (1, vec![NodeId(a), NodeId(b), NodeId(c)]));
+///
+/// // `a` is not reachable from `c`
+/// assert_eq!(dag_shortest_path(&graph, Some(c.into()), Some(a.into()), |e| e.data.unwrap()), Ok(None));
+/// ```
+pub fn dag_shortest_path<N, E, W, F>(graph: &Graph<N, E>, source: Option<NodeId>, target: Option<NodeId>, weight: F)
+                                     -> Result<Option<DagPath<W>>, GraphErr>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone,
+          W: Copy + PartialOrd + Add<Output = W> + Default, F: Fn(&Edge<E>) -> W
+{
+    dag_optimal_path(graph, source, target, weight, |a, b| a < b)
+}
+
+// Dynamic programming over the nodes in topological order, keeping the best path ending at every node
+fn dag_optimal_path<N, E, W, F, B>(graph: &Graph<N, E>, source: Option<NodeId>, target: Option<NodeId>, weight: F, better: B)
+                                  -> Result<Option<DagPath<W>>, GraphErr>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone,
+          W: Copy + PartialOrd + Add<Output = W> + Default, F: Fn(&Edge<E>) -> W, B: Fn(W, W) -> bool
+{
+    if source.iter().chain(target.iter()).any(|&id| graph.node(id).is_none()) {
+        return Err(GraphErr::NoSuchVertex);
+    }
+    let order = topological_sort(graph)?;
+
+    // Weight of the best path ending at every node, with the last edge of the path
+    let mut best: Vec<Option<(W, Option<EdgeId>)>> = vec![None; graph.node_bound()];
+    match source {
+        Some(source) => best[source.index()] = Some((W::default(), None)),
+        None => order.iter().for_each(|&node| best[node.index()] = Some((W::default(), None))),
+    }
+
+    for &node in &order {
+        let Some((total, _)) = best[node.index()] else {
+            continue;
+        };
+        for &edge_id in graph.out_edges(node.into()) {
+            let edge = graph.get_edge(&edge_id);
+            let candidate = total + weight(edge);
+            if best[edge.end as usize].is_none_or(|(w, _)| better(candidate, w)) {
+                best[edge.end as usize] = Some((candidate, Some(EdgeId(edge_id))));
+            }
+        }
+    }
+
+    // Last node of the path, the first optimal node in topological order if not given
+    let last = match target {
+        Some(target) => target,
+        None => {
+            let mut last = None;
+            for &node in &order {
+                if let Some((w, _)) = best[node.index()] {
+                    if last.is_none_or(|(_, l)| better(w, l)) {
+                        last = Some((node, w));
+                    }
+                }
+            }
+            match last {
+                Some((node, _)) => node,
+                None => return Ok(None),
+            }
+        }
+    };
+    let Some((total, _)) = best[last.index()] else {
+        return Ok(None);
+    };
+
+    // Trace the path back along the recorded edges
+    let mut nodes = vec![last];
+    let mut edges = Vec::<EdgeId>::new();
+    while let Some((_, Some(edge))) = best[nodes.last().unwrap().index()] {
+        edges.push(edge);
+        nodes.push(NodeId(graph.get_edge(&edge.into()).start));
+    }
+    nodes.reverse();
+    edges.reverse();
+    Ok(Some(DagPath { nodes, edges, weight: total }))
+}
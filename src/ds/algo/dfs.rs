@@ -4,6 +4,7 @@ use std::collections::HashSet;
 use crate::ds::graph::Graph;
 use crate::ds::graph::GraphErr;
 
+/// Step-wise depth-first search, see `traversal::DfsPreorder` for an iterator over the nodes
 pub struct Dfs<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> {
     current: u64,
     stack: Vec<u64>,
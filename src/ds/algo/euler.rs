@@ -0,0 +1,205 @@
+use std::fmt;
+
+use crate::ds::graph::{Graph, GraphErr, NodeId};
+use crate::ds::sequence::Sequence;
+
+use super::components::weakly_connected_components;
+
+/// Path traversing every edge of a graph exactly once
+#[derive(Debug, Clone, PartialEq)]
+pub struct EulerianPath {
+    /// Nodes along the path, one more than there are edges
+    pub nodes: Vec<u64>,
+    /// Edges in the order they are traversed
+    pub edges: Vec<u64>,
+}
+
+impl EulerianPath {
+
+    /// Whether the path ends at the node it starts at
+    pub fn is_circuit(&self) -> bool {
+        !self.edges.is_empty() && self.nodes.first() == self.nodes.last()
+    }
+}
+
+/// Construct a path traversing every edge of a directed graph exactly once, using Hierholzer's algorithm
+///
+/// Complexity: O(V + E)
+///
+/// Unlike `cycles::eulerian_circuit`, the graph does not need to be balanced and no start node has to be given.
+/// An Eulerian path exists, if all edges are weakly connected and every node has as many incoming as outgoing edges,
+/// except for at most one start node with an additional outgoing edge and one end node with an additional incoming edge.
+/// The path starts at the start node if present, otherwise it is a circuit starting at the node with the smallest id.
+/// Fails with `UnbalancedNode`, if the degrees of a node rule out a path,
+/// and with `DisconnectedGraph`, if the edges belong to several weakly connected components.
+/// A graph without edges yields an empty path.
+///
+/// A De Bruijn graph of a linear genome without repeats has a single Eulerian path spelling the genome, see `spell_path`.
+///
+/// # Arguments
+/// * `graph` - directed graph
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::euler::eulerian_path;
+/// use biogarden::ds::graph::{Graph, GraphErr, GraphProperties};
+///
+/// let mut graph = Graph::<char, u8>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// graph.add_edge(&b, &c, None).unwrap();
+/// graph.add_edge(&c, &b, None).unwrap();
+/// graph.add_edge(&a, &b, None).unwrap();
+/// graph.add_edge(&b, &c, None).unwrap();
+///
+/// // Path has to start at `a`, the only node with more outgoing than incoming edges
+/// let path = eulerian_path(&graph).unwrap();
+/// assert_eq!(path.nodes, vec![a, b, c, b, c]);
+/// assert!(!path.is_circuit());
+///
+/// // Both `a` and `b` now have more outgoing than incoming edges
+/// graph.add_edge(&b, &c, None).unwrap();
+/// assert_eq!(eulerian_path(&graph), Err(GraphErr::UnbalancedNode(b)));
+/// ```
+pub fn eulerian_path<N, E>(graph: &Graph<N, E>) -> Result<EulerianPath, GraphErr>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone
+{
+    let mut paths = eulerian_paths(graph)?;
+    match paths.len() {
+        0 => Ok(EulerianPath { nodes: vec![], edges: vec![] }),
+        1 => Ok(paths.pop().unwrap()),
+        _ => Err(GraphErr::DisconnectedGraph),
+    }
+}
+
+/// Construct an Eulerian path for every weakly connected component of a directed graph, which contains edges
+///
+/// Complexity: O(V + E)
+///
+/// Components are processed independently and ordered by their smallest node id, nodes without edges are ignored.
+/// This is the case for De Bruijn graphs including both strands, or built from several chromosomes.
+/// Fails with `UnbalancedNode`, if the degrees of a node rule out a path within its component.
+///
+/// # Arguments
+/// * `graph` - directed graph
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::euler::{eulerian_path, eulerian_paths};
+/// use biogarden::ds::graph::{Graph, GraphErr, GraphProperties};
+///
+/// let mut graph = Graph::<char, u8>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// let d = graph.add_node('d');
+/// graph.add_edge(&a, &b, None).unwrap();
+/// graph.add_edge(&c, &d, None).unwrap();
+/// graph.add_edge(&d, &c, None).unwrap();
+///
+/// let paths = eulerian_paths(&graph).unwrap();
+/// assert_eq!(paths[0].nodes, vec![a, b]);
+/// assert_eq!(paths[1].nodes, vec![c, d, c]);
+/// assert!(paths[1].is_circuit());
+///
+/// // A single path cannot cover both components
+/// assert_eq!(eulerian_path(&graph), Err(GraphErr::DisconnectedGraph));
+/// ```
+pub fn eulerian_paths<N, E>(graph: &Graph<N, E>) -> Result<Vec<EulerianPath>, GraphErr>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone
+{
+    // Position of the next unused outgoing edge of every node, shared by all components
+    let mut next = vec![0; graph.node_bound()];
+    let mut paths = Vec::<EulerianPath>::new();
+
+    for component in weakly_connected_components(graph) {
+        let start = match find_start(graph, &component)? {
+            Some(start) => start,
+            None => continue,
+        };
+
+        // Walk along unused edges until getting stuck, edges are emitted once their end has no unused edges left,
+        // which splices the detours taken from intermediate nodes into the path
+        let mut stack: Vec<(u64, Option<u64>)> = vec![(start, None)];
+        let mut edges = Vec::<u64>::new();
+        while let Some(&(node, _)) = stack.last() {
+            let outgoing = graph.get_node(&node).outgoing();
+            if next[node as usize] < outgoing.len() {
+                let edge = outgoing[next[node as usize]];
+                next[node as usize] += 1;
+                stack.push((graph.get_edge(&edge).end, Some(edge)));
+            } else if let (_, Some(edge)) = stack.pop().unwrap() {
+                edges.push(edge);
+            }
+        }
+        edges.reverse();
+
+        let mut nodes = vec![start];
+        nodes.extend(edges.iter().map(|edge| graph.get_edge(edge).end));
+        paths.push(EulerianPath { nodes, edges });
+    }
+    Ok(paths)
+}
+
+// Determine the start node of the Eulerian path within a weakly connected component from the degree imbalance,
+// `None` if the component does not contain any edges
+fn find_start<N, E>(graph: &Graph<N, E>, component: &[NodeId]) -> Result<Option<u64>, GraphErr>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone
+{
+    let mut start = None;
+    let mut end = None;
+    let mut first = None;
+    for id in component.iter().map(|&id| u64::from(id)) {
+        let node = graph.get_node(&id);
+        if first.is_none() && !node.outgoing().is_empty() {
+            first = Some(id);
+        }
+        match node.outgoing().len() as i64 - node.incoming().len() as i64 {
+            0 => {}
+            1 if start.is_none() => start = Some(id),
+            -1 if end.is_none() => end = Some(id),
+            _ => return Err(GraphErr::UnbalancedNode(id)),
+        }
+    }
+    // A start without an end, or vice versa, cannot occur as the imbalances sum up to zero
+    Ok(start.or(first))
+}
+
+/// Spell the sequence along a path of a De Bruijn graph
+///
+/// Consecutive nodes are expected to overlap by all but one letter, as the (k-1)-mers of a De Bruijn graph do.
+/// The sequence consists of the first node, followed by the last letter of every further node.
+/// For a circuit, the sequence ends with its first k-1 letters again, which can be trimmed for circular genomes.
+///
+/// # Arguments
+/// * `graph` - graph with sequences as nodes, e.g. built by `builders::debruijn::DeBruijnBuilder`
+/// * `nodes` - nodes along the path, e.g. `EulerianPath::nodes`
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::euler::{eulerian_path, spell_path};
+/// use biogarden::ds::builders::debruijn::DeBruijnBuilder;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// let mut kmers = Tile::new();
+/// for kmer in ["TAAT", "GCCA", "ATGC", "CCAT", "TGCC", "AATG"] {
+///     kmers.push(Sequence::from(kmer));
+/// }
+/// let graph = DeBruijnBuilder::new().build(&kmers);
+/// let path = eulerian_path(&graph).unwrap();
+/// assert_eq!(spell_path(&graph, &path.nodes), Sequence::from("TAATGCCAT"));
+/// ```
+pub fn spell_path<E>(graph: &Graph<Sequence, E>, nodes: &[u64]) -> Sequence
+    where E: fmt::Display + Clone
+{
+    let mut sequence = Sequence::new();
+    if let Some((first, rest)) = nodes.split_first() {
+        sequence.chain.extend(&graph.get_node(first).data.chain);
+        for node in rest {
+            sequence.chain.extend(graph.get_node(node).data.chain.last());
+        }
+    }
+    sequence
+}
@@ -6,71 +6,25 @@ use crate::ds::sequence::Sequence;
 use crate::ds::graph::Graph;
 use crate::ds::graph::GraphProperties;
 
-use super::dfs::Dfs;
+use super::components::strongly_connected_components;
 
-/// Connected components based on Kosaraju's Algorithm O(V+E)
+/// Strongly connected components O(V+E)
+///
+/// Kept for compatibility, the graph is not modified.
+/// See `components::strongly_connected_components` and `components::weakly_connected_components`.
 pub fn connected_components<N, E>(g: &mut Graph<N, E>) -> Vec<HashSet::<u64>> 
             where N: fmt::Display + Clone , E: fmt::Display + Clone
 {
-
-    // Define dfs with backtrack, where nodes are added to stack after all their children are processed
-    fn dfs_backtrack<N, E>(g: &Graph<N, E>, nid: u64, stack: &mut Vec::<u64>, visited: &mut HashSet::<u64>) 
-        where N: fmt::Display  + Clone, E: fmt::Display + Clone
-    {
-
-        if visited.contains(&nid) {
-            return;
-        }
-
-        visited.insert(nid);
-        
-        for node in g.out_neighbors(nid) {
-            dfs_backtrack(g, *node, stack, visited);
-        }
-
-        stack.push(nid);
-
-    }
-
-    // Run the backtrack DFS
-    let mut visited = HashSet::<u64>::new();
-    let mut stack = Vec::<u64>::new();
-
-    for n in g.nodes() {
-        if !visited.contains(n) {
-            dfs_backtrack(g, *n, &mut stack, &mut visited);
-        }
-    }
-
-    // Reverse graph
-    g.reverse();
-
-    // Stack based DFS, no backtracking needed
-    let mut dfs = Dfs::new(g);
-    let mut cc = HashSet::<u64>::new();
-    let mut components : Vec<HashSet::<u64>> = vec![];
-    let mut processed = HashSet::<u64>::new();
-
-    while !stack.is_empty() {
-        let n = stack.pop().unwrap();
-        if !processed.contains(&n) {
-            dfs.init(&n);
-            while let Ok(id) = dfs.process_node() {
-                processed.insert(id);
-                cc.insert(id);
-            }
-            components.push(cc.clone());
-            cc.clear();
-        }
-    }
-
-    // Reverse graph back to its initial form
-    g.reverse();
-
-    components
+    strongly_connected_components(g)
+        .into_iter()
+        .map(|component| component.into_iter().map(u64::from).collect())
+        .collect()
 }
 
 /// Build a k-overlap graph using a set of sequences 
+///
+/// Only exact overlaps of length `k` are detected, by comparing all pairs of sequences.
+/// See `ds::builders::string_graph::StringGraphBuilder` for approximate overlaps of any length.
 pub fn overlap_graph(sequences: &Tile, k: usize) -> Graph::<Sequence, u8> {
 
     // Instantiate empty graph
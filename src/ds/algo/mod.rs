@@ -3,4 +3,16 @@ pub mod cycles;
 /// Depth-first Search
 pub mod dfs;
 /// Miscellaneous: connected components, overlap graph
-pub mod misc;
\ No newline at end of file
+pub mod misc;
+/// De Bruijn graph assembly: unitigs, tip clipping, bubble popping and contigs
+pub mod assembly;
+/// Shortest paths: breadth-first search, Dijkstra, Bellman-Ford
+pub mod paths;
+/// Directed acyclic graphs: topological sort, longest and shortest paths
+pub mod dag;
+/// Strongly and weakly connected components, condensation
+pub mod components;
+/// Eulerian paths and circuits, spelling paths of De Bruijn graphs
+pub mod euler;
+/// Breadth-first and depth-first traversal iterators
+pub mod traversal;
@@ -0,0 +1,251 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt;
+use std::ops::Add;
+
+use crate::ds::graph::{Edge, EdgeId, Graph, GraphErr, NodeId};
+
+/// Shortest paths from a single source node to all reachable nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPaths<W> {
+    /// Node the paths start at
+    pub source: NodeId,
+    /// Length of the shortest path to every reachable node
+    pub distances: HashMap<NodeId, W>,
+    /// Last edge on the shortest path to every reachable node, except for the source
+    pub predecessors: HashMap<NodeId, EdgeId>,
+}
+
+impl<W: Copy> ShortestPaths<W> {
+
+    /// Length of the shortest path to `target`, `None` if it is not reachable
+    pub fn distance(&self, target: impl Into<NodeId>) -> Option<W> {
+        self.distances.get(&target.into()).copied()
+    }
+
+    /// Edges along the shortest path to `target`, `None` if it is not reachable
+    pub fn edge_path<N, E>(&self, graph: &Graph<N, E>, target: impl Into<NodeId>) -> Option<Vec<EdgeId>>
+        where N: fmt::Display + Clone, E: fmt::Display + Clone
+    {
+        let mut node = target.into();
+        if !self.distances.contains_key(&node) {
+            return None;
+        }
+        let mut edges = Vec::<EdgeId>::new();
+        while let Some(&edge) = self.predecessors.get(&node) {
+            edges.push(edge);
+            node = NodeId(graph.get_edge(&edge.into()).start);
+        }
+        edges.reverse();
+        Some(edges)
+    }
+
+    /// Nodes along the shortest path to `target`, starting with the source, `None` if it is not reachable
+    pub fn path<N, E>(&self, graph: &Graph<N, E>, target: impl Into<NodeId>) -> Option<Vec<NodeId>>
+        where N: fmt::Display + Clone, E: fmt::Display + Clone
+    {
+        let edges = self.edge_path(graph, target)?;
+        let mut nodes = vec![self.source];
+        nodes.extend(edges.iter().map(|&edge| NodeId(graph.get_edge(&edge.into()).end)));
+        Some(nodes)
+    }
+}
+
+/// Compute the number of edges on shortest paths from a source node, using breadth-first search
+///
+/// Complexity: O(V + E)
+///
+/// Edges are followed from their start to their end node.
+///
+/// # Arguments
+/// * `graph` - graph to search
+/// * `source` - node the paths start at
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::paths::bfs_distances;
+/// use biogarden::ds::graph::{Graph, GraphProperties, NodeId};
+///
+/// let mut graph = Graph::<char, u8>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// let d = graph.add_node('d');
+/// graph.add_edge(&a, &b, None).unwrap();
+/// graph.add_edge(&b, &c, None).unwrap();
+/// graph.add_edge(&a, &c, None).unwrap();
+///
+/// let paths = bfs_distances(&graph, a).unwrap();
+/// assert_eq!(paths.distance(c), Some(1));
+/// assert_eq!(paths.path(&graph, c), Some(vec![NodeId(a), NodeId(c)]));
+/// assert_eq!(paths.distance(d), None);
+/// ```
+pub fn bfs_distances<N, E>(graph: &Graph<N, E>, source: impl Into<NodeId>) -> Result<ShortestPaths<usize>, GraphErr>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone
+{
+    let source = source.into();
+    if graph.node(source).is_none() {
+        return Err(GraphErr::NoSuchVertex);
+    }
+
+    let mut paths = ShortestPaths { source, distances: HashMap::from([(source, 0)]), predecessors: HashMap::new() };
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        let distance = paths.distances[&node];
+        for &edge in graph.out_edges(node.into()) {
+            let next = NodeId(graph.get_edge(&edge).end);
+            if let Entry::Vacant(entry) = paths.distances.entry(next) {
+                entry.insert(distance + 1);
+                paths.predecessors.insert(next, EdgeId(edge));
+                queue.push_back(next);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Compute shortest paths from a source node in a graph with non-negative edge weights, using Dijkstra's algorithm
+///
+/// Complexity: O((V + E) log V)
+///
+/// Weights are obtained from the edges by a closure, e.g. `|e| e.data.unwrap()`, and may be of any ordered type
+/// with addition, whose `Default` value is zero. Fails with `InvalidWeight`, if a negative weight is encountered.
+///
+/// # Arguments
+/// * `graph` - graph to search
+/// * `source` - node the paths start at
+/// * `weight` - weight of an edge
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::paths::dijkstra;
+/// use biogarden::ds::graph::{EdgeId, Graph, GraphProperties, NodeId};
+///
+/// let mut graph = Graph::<char, u32>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// graph.add_edge(&a, &b, Some(2)).unwrap();
+/// graph.add_edge(&b, &c, Some(3)).unwrap();
+/// let ac = graph.add_edge(&a, &c, Some(7)).unwrap();
+///
+/// let paths = dijkstra(&graph, a, |e| e.data.unwrap()).unwrap();
+/// assert_eq!(paths.distance(c), Some(5));
+/// assert_eq!(paths.path(&graph, c), Some(vec![NodeId(a), NodeId(b), NodeId(c)]));
+///
+/// // Direct edge becomes shorter
+/// graph.get_edge_mut(&ac).data = Some(4);
+/// let paths = dijkstra(&graph, a, |e| e.data.unwrap()).unwrap();
+/// assert_eq!(paths.edge_path(&graph, c), Some(vec![EdgeId(ac)]));
+/// ```
+pub fn dijkstra<N, E, W, F>(graph: &Graph<N, E>, source: impl Into<NodeId>, weight: F) -> Result<ShortestPaths<W>, GraphErr>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone,
+          W: Copy + Ord + Add<Output = W> + Default, F: Fn(&Edge<E>) -> W
+{
+    let source = source.into();
+    if graph.node(source).is_none() {
+        return Err(GraphErr::NoSuchVertex);
+    }
+
+    let mut paths = ShortestPaths { source, distances: HashMap::from([(source, W::default())]), predecessors: HashMap::new() };
+    let mut settled = vec![false; graph.node_bound()];
+    let mut heap = BinaryHeap::from([Reverse((W::default(), source))]);
+
+    while let Some(Reverse((distance, node))) = heap.pop() {
+        // Skip outdated entries of nodes, whose distance has been lowered in the meantime
+        if settled[node.index()] {
+            continue;
+        }
+        settled[node.index()] = true;
+
+        for &edge_id in graph.out_edges(node.into()) {
+            let edge = graph.get_edge(&edge_id);
+            let w = weight(edge);
+            if w < W::default() {
+                return Err(GraphErr::InvalidWeight);
+            }
+            let candidate = distance + w;
+            let end = NodeId(edge.end);
+            if paths.distances.get(&end).is_none_or(|&d| candidate < d) {
+                paths.distances.insert(end, candidate);
+                paths.predecessors.insert(end, EdgeId(edge_id));
+                heap.push(Reverse((candidate, end)));
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Compute shortest paths from a source node in a graph with arbitrary edge weights, using the Bellman-Ford algorithm
+///
+/// Complexity: O(VE)
+///
+/// Weights are obtained from the edges by a closure and may be negative.
+/// Fails with `NegativeCycle`, if a cycle of negative weight is reachable from the source,
+/// as paths can be shortened indefinitely by traversing it.
+///
+/// # Arguments
+/// * `graph` - graph to search
+/// * `source` - node the paths start at
+/// * `weight` - weight of an edge
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::paths::bellman_ford;
+/// use biogarden::ds::graph::{Graph, GraphErr, GraphProperties, NodeId};
+///
+/// let mut graph = Graph::<char, i32>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// graph.add_edge(&a, &b, Some(4)).unwrap();
+/// graph.add_edge(&b, &c, Some(-3)).unwrap();
+/// graph.add_edge(&a, &c, Some(2)).unwrap();
+///
+/// let paths = bellman_ford(&graph, a, |e| e.data.unwrap()).unwrap();
+/// assert_eq!(paths.distance(c), Some(1));
+/// assert_eq!(paths.path(&graph, c), Some(vec![NodeId(a), NodeId(b), NodeId(c)]));
+///
+/// // Cycle b -> c -> b of weight -1
+/// graph.add_edge(&c, &b, Some(2)).unwrap();
+/// assert_eq!(bellman_ford(&graph, a, |e| e.data.unwrap()), Err(GraphErr::NegativeCycle));
+/// ```
+pub fn bellman_ford<N, E, W, F>(graph: &Graph<N, E>, source: impl Into<NodeId>, weight: F) -> Result<ShortestPaths<W>, GraphErr>
+    where N: fmt::Display + Clone, E: fmt::Display + Clone,
+          W: Copy + Ord + Add<Output = W> + Default, F: Fn(&Edge<E>) -> W
+{
+    let source = source.into();
+    if graph.node(source).is_none() {
+        return Err(GraphErr::NoSuchVertex);
+    }
+
+    let mut paths = ShortestPaths { source, distances: HashMap::from([(source, W::default())]), predecessors: HashMap::new() };
+    let edges: Vec<u64> = graph.edges().cloned().collect();
+
+    // Relax all edges, shortest paths without cycles consist of at most V - 1 edges
+    for round in 0..graph.node_count() {
+        let mut changed = false;
+        for &edge_id in &edges {
+            let edge = graph.get_edge(&edge_id);
+            let Some(&distance) = paths.distances.get(&NodeId(edge.start)) else {
+                continue;
+            };
+            let candidate = distance + weight(edge);
+            let end = NodeId(edge.end);
+            if paths.distances.get(&end).is_none_or(|&d| candidate < d) {
+                // Distances still decreasing after V - 1 rounds are caused by a negative cycle
+                if round + 1 == graph.node_count() {
+                    return Err(GraphErr::NegativeCycle);
+                }
+                paths.distances.insert(end, candidate);
+                paths.predecessors.insert(end, EdgeId(edge_id));
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    Ok(paths)
+}
@@ -0,0 +1,269 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::ds::graph::{Edge, Graph, GraphErr};
+
+type EdgeFilter<'a, E> = Box<dyn FnMut(&Edge<E>) -> bool + 'a>;
+type EdgeOrder<'a, E> = Box<dyn Fn(&Edge<E>, &Edge<E>) -> Ordering + 'a>;
+
+// Decides which edges are followed and in which order, shared by all traversals
+struct Walker<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> {
+    graph: &'a Graph<N, E>,
+    reversed: bool,
+    filter: Option<EdgeFilter<'a, E>>,
+    order: Option<EdgeOrder<'a, E>>,
+    visited: Vec<bool>,
+}
+
+impl<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> Walker<'a, N, E> {
+
+    fn new(graph: &'a Graph<N, E>, start: u64) -> Result<Self, GraphErr> {
+        if !graph.contains_node(&start) {
+            return Err(GraphErr::NoSuchVertex);
+        }
+        Ok(Walker { graph, reversed: false, filter: None, order: None, visited: vec![false; graph.node_bound()] })
+    }
+
+    // Nodes reached from `node` along edges accepted by the filter, which have not been visited yet
+    fn successors(&mut self, node: u64) -> Vec<u64> {
+        let graph = self.graph;
+        let ids = match self.reversed {
+            false => graph.get_node(&node).outgoing(),
+            true => graph.get_node(&node).incoming(),
+        };
+        let mut edges: Vec<&Edge<E>> = ids.iter().map(|id| graph.get_edge(id)).collect();
+        if let Some(order) = &self.order {
+            edges.sort_by(|a, b| order(a, b));
+        }
+
+        let mut successors = Vec::<u64>::with_capacity(edges.len());
+        for edge in edges {
+            let next = if self.reversed { edge.start } else { edge.end };
+            if self.visited[next as usize] || !self.filter.as_mut().is_none_or(|filter| filter(edge)) {
+                continue;
+            }
+            successors.push(next);
+        }
+        successors
+    }
+}
+
+// Builder methods common to all traversals
+macro_rules! traversal_options {
+    ($name:ident) => {
+        impl<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> $name<'a, N, E> {
+
+            /// Follow edges from their end to their start node, i.e. along `in_edges`
+            pub fn reversed(mut self) -> Self {
+                self.walker.reversed = true;
+                self
+            }
+
+            /// Only follow edges, for which `filter` returns true, pruning the part of the graph only reachable through other edges
+            pub fn with_filter<F: FnMut(&Edge<E>) -> bool + 'a>(mut self, filter: F) -> Self {
+                self.walker.filter = Some(Box::new(filter));
+                self
+            }
+
+            /// Follow the edges of a node in ascending order of `key`, instead of the order they were added in
+            pub fn with_order<K: Ord, F: Fn(&Edge<E>) -> K + 'a>(mut self, key: F) -> Self {
+                self.walker.order = Some(Box::new(move |a, b| key(a).cmp(&key(b))));
+                self
+            }
+        }
+    };
+}
+
+/// Iterator over the nodes reachable from a start node in breadth-first order
+///
+/// Complexity: O(V + E)
+///
+/// Nodes are yielded in order of increasing number of edges from the start node, which is yielded first.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::traversal::Bfs;
+/// use biogarden::ds::graph::{Graph, GraphProperties};
+///
+/// let mut graph = Graph::<char, u8>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// let d = graph.add_node('d');
+/// graph.add_edge(&a, &b, None).unwrap();
+/// graph.add_edge(&b, &d, None).unwrap();
+/// graph.add_edge(&a, &c, None).unwrap();
+///
+/// assert_eq!(Bfs::new(&graph, a).unwrap().collect::<Vec<u64>>(), vec![a, b, c, d]);
+/// // Along incoming edges
+/// assert_eq!(Bfs::new(&graph, d).unwrap().reversed().collect::<Vec<u64>>(), vec![d, b, a]);
+/// ```
+pub struct Bfs<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> {
+    walker: Walker<'a, N, E>,
+    queue: VecDeque<u64>,
+}
+
+impl<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> Bfs<'a, N, E> {
+
+    /// Start the traversal at `start`, fails with `NoSuchVertex` if it does not exist
+    pub fn new(graph: &'a Graph<N, E>, start: u64) -> Result<Self, GraphErr> {
+        let mut walker = Walker::new(graph, start)?;
+        walker.visited[start as usize] = true;
+        Ok(Bfs { walker, queue: VecDeque::from([start]) })
+    }
+}
+
+traversal_options!(Bfs);
+
+impl<N: fmt::Display + Clone, E: fmt::Display + Clone> Iterator for Bfs<'_, N, E> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let node = self.queue.pop_front()?;
+        // Mark nodes when they are queued, such that every node is queued once
+        for next in self.walker.successors(node) {
+            self.walker.visited[next as usize] = true;
+            self.queue.push_back(next);
+        }
+        Some(node)
+    }
+}
+
+/// Iterator over the nodes reachable from a start node in depth-first preorder
+///
+/// Complexity: O(V + E)
+///
+/// Every node is yielded before the nodes discovered from it. With `with_order`, the nodes of a trie are yielded
+/// in lexicographic order of the words they spell.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::traversal::DfsPreorder;
+/// use biogarden::ds::graph::{Graph, GraphProperties};
+///
+/// let mut graph = Graph::<char, u8>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// let d = graph.add_node('d');
+/// graph.add_edge(&a, &b, Some(2)).unwrap();
+/// graph.add_edge(&b, &d, Some(1)).unwrap();
+/// graph.add_edge(&a, &c, Some(1)).unwrap();
+///
+/// assert_eq!(DfsPreorder::new(&graph, a).unwrap().collect::<Vec<u64>>(), vec![a, b, d, c]);
+///
+/// // Visit edges of smaller weight first
+/// let order = DfsPreorder::new(&graph, a).unwrap().with_order(|e| e.data);
+/// assert_eq!(order.collect::<Vec<u64>>(), vec![a, c, b, d]);
+///
+/// // Prune the subtree below `b`
+/// let order = DfsPreorder::new(&graph, a).unwrap().with_filter(|e| e.start != b);
+/// assert_eq!(order.collect::<Vec<u64>>(), vec![a, b, c]);
+/// ```
+pub struct DfsPreorder<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> {
+    walker: Walker<'a, N, E>,
+    stack: Vec<u64>,
+}
+
+impl<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> DfsPreorder<'a, N, E> {
+
+    /// Start the traversal at `start`, fails with `NoSuchVertex` if it does not exist
+    pub fn new(graph: &'a Graph<N, E>, start: u64) -> Result<Self, GraphErr> {
+        Ok(DfsPreorder { walker: Walker::new(graph, start)?, stack: vec![start] })
+    }
+}
+
+traversal_options!(DfsPreorder);
+
+impl<N: fmt::Display + Clone, E: fmt::Display + Clone> Iterator for DfsPreorder<'_, N, E> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        // Nodes may be pushed several times before they are visited, only the topmost entry counts
+        let node = loop {
+            let node = self.stack.pop()?;
+            if !self.walker.visited[node as usize] {
+                break node;
+            }
+        };
+        self.walker.visited[node as usize] = true;
+        // Push in reverse order, such that the first successor is visited first
+        let successors = self.walker.successors(node);
+        self.stack.extend(successors.into_iter().rev());
+        Some(node)
+    }
+}
+
+/// Iterator over the nodes reachable from a start node in depth-first postorder
+///
+/// Complexity: O(V + E)
+///
+/// Every node is yielded after all nodes discovered from it, the start node is yielded last.
+/// In a tree, children are processed before their parent, e.g. to aggregate values from the leaves upwards.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::algo::traversal::DfsPostorder;
+/// use biogarden::ds::graph::{Graph, GraphProperties};
+///
+/// let mut graph = Graph::<char, u8>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// let d = graph.add_node('d');
+/// graph.add_edge(&a, &b, None).unwrap();
+/// graph.add_edge(&b, &d, None).unwrap();
+/// graph.add_edge(&a, &c, None).unwrap();
+/// graph.add_edge(&c, &d, None).unwrap();
+///
+/// assert_eq!(DfsPostorder::new(&graph, a).unwrap().collect::<Vec<u64>>(), vec![d, b, c, a]);
+/// ```
+pub struct DfsPostorder<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> {
+    walker: Walker<'a, N, E>,
+    // Nodes of the simulated recursion, with their remaining successors in reverse order once expanded
+    stack: Vec<(u64, Option<Vec<u64>>)>,
+}
+
+impl<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> DfsPostorder<'a, N, E> {
+
+    /// Start the traversal at `start`, fails with `NoSuchVertex` if it does not exist
+    pub fn new(graph: &'a Graph<N, E>, start: u64) -> Result<Self, GraphErr> {
+        let mut walker = Walker::new(graph, start)?;
+        walker.visited[start as usize] = true;
+        Ok(DfsPostorder { walker, stack: vec![(start, None)] })
+    }
+}
+
+traversal_options!(DfsPostorder);
+
+impl<N: fmt::Display + Clone, E: fmt::Display + Clone> Iterator for DfsPostorder<'_, N, E> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            let (node, pending) = self.stack.last_mut()?;
+            let node = *node;
+            let pending = match pending {
+                Some(pending) => pending,
+                None => {
+                    let mut successors = self.walker.successors(node);
+                    successors.reverse();
+                    pending.insert(successors)
+                }
+            };
+            match pending.pop() {
+                // Successors may have been visited through another path in the meantime
+                Some(next) if !self.walker.visited[next as usize] => {
+                    self.walker.visited[next as usize] = true;
+                    self.stack.push((next, None));
+                }
+                Some(_) => {}
+                None => {
+                    self.stack.pop();
+                    return Some(node);
+                }
+            }
+        }
+    }
+}
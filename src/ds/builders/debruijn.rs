@@ -4,61 +4,160 @@ use crate::ds::tile::Tile;
 use crate::ds::sequence::Sequence;
 use crate::ds::graph::Graph;
 use crate::ds::graph::GraphProperties;
+use crate::processing::kmers::encode_base;
+use crate::processing::transformers::reverse_complement;
 
 /// Build DeBruijn graph
-pub struct DeBruijnBuilder {}
+///
+/// Every k-mer of the input contributes an edge between its (k-1)-mer prefix and suffix.
+/// By default each input sequence is treated as a single k-mer, with `with_k` reads of arbitrary length are split into k-mers.
+/// Every occurrence of a k-mer yields its own unlabelled edge, such that repeated k-mers form parallel edges
+/// and an Eulerian path spells the input (see `algo::euler`).
+/// With `with_counts`, identical k-mers are merged into a single edge labelled with their number of occurrences,
+/// as expected by the error removal in `algo::assembly`.
+/// Optionally, reverse complements of the k-mers are included, such that the graph represents both strands.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::builders::debruijn::DeBruijnBuilder;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// let mut kmers = Tile::new();
+/// kmers.push(Sequence::from("AAT"));
+///
+/// // AA -> AT
+/// let graph = DeBruijnBuilder::new().build(&kmers);
+/// assert_eq!((graph.node_count(), graph.edge_count()), (2, 1));
+///
+/// // AA -> AT, AT -> TT (reverse complement ATT)
+/// let graph = DeBruijnBuilder::new().with_both_strands(true).build(&kmers);
+/// assert_eq!((graph.node_count(), graph.edge_count()), (3, 2));
+///
+/// // Reads split into 3-mers: GAT, ATT, TTA, TAC, ACA, CAT, ATT
+/// let mut reads = Tile::new();
+/// reads.push(Sequence::from("GATTACATT"));
+/// let graph = DeBruijnBuilder::new().with_k(3).build(&reads);
+/// assert_eq!((graph.node_count(), graph.edge_count()), (6, 7));
+///
+/// // ATT occurs twice
+/// let graph = DeBruijnBuilder::new().with_k(3).with_counts(true).build(&reads);
+/// assert_eq!((graph.node_count(), graph.edge_count()), (6, 6));
+/// let id = graph.edges().find(|&e| graph.get_edge(e).data == Some(2)).unwrap();
+/// assert_eq!(graph.get_node(&graph.get_edge(id).start).data, Sequence::from("AT"));
+/// ```
+pub struct DeBruijnBuilder {
+    both_strands: bool,
+    k: Option<usize>,
+    counts: bool,
+    min_count: usize,
+    nucleotides_only: bool,
+}
 
 impl DeBruijnBuilder {
 
     pub fn new() -> DeBruijnBuilder {
-        DeBruijnBuilder {}
+        DeBruijnBuilder { both_strands: false, k: None, counts: false, min_count: 1, nucleotides_only: false }
+    }
+
+    /// Include reverse complements of the k-mers, see `processing::transformers::reverse_complement`
+    pub fn with_both_strands(mut self, both_strands: bool) -> Self {
+        self.both_strands = both_strands;
+        self
+    }
+
+    /// Split reads into all of their k-mers of length `k`, reads shorter than `k` are skipped
+    pub fn with_k(mut self, k: usize) -> Self {
+        self.k = Some(k);
+        self
+    }
+
+    /// Merge identical k-mers into a single edge, labelled with the number of occurrences of the k-mer
+    pub fn with_counts(mut self, counts: bool) -> Self {
+        self.counts = counts;
+        self
+    }
+
+    /// Discard k-mers occurring less than `min_count` times, which likely contain sequencing errors
+    ///
+    /// Counting requires identical k-mers to be merged, i.e. implies `with_counts(true)`.
+    pub fn with_min_count(mut self, min_count: usize) -> Self {
+        self.counts = true;
+        self.min_count = min_count;
+        self
+    }
+
+    /// Compare letters case-insensitively and skip k-mers containing letters other than A, C, G, T/U
+    pub fn with_nucleotides_only(mut self, nucleotides_only: bool) -> Self {
+        self.nucleotides_only = nucleotides_only;
+        self
     }
 
     pub fn build(&self, reads: &Tile) -> Graph<Sequence, usize>  {
 
         let mut graph = Graph::<Sequence, usize>::new(GraphProperties{directed: true});
         let mut present_nodes = HashMap::<Sequence, u64>::new();
-        
-        // Preallocate kmerid holders
-        let mut kmer1_id = 0_u64;
-        let mut kmer2_id = 0_u64;
-    
+
         // Pre-calculate reverse complements of input reads
-        let rev_reads = Tile::new();
-        // reads.into_iter().for_each(|kmer|{ rev_reads.push(complement_dna(kmer.clone()))});
-    
-        // Construct deBruijn graph, iterate over all kmers and their complements 
-        for kmer in reads.into_iter().chain(rev_reads.into_iter().as_ref()) {
-    
-            let kmer1 = Sequence::from(&kmer[0..kmer.len()-1]);
-            let kmer2 = Sequence::from(&kmer[1..kmer.len()]);
-    
-            // Process Kmer1
-            if present_nodes.contains_key(&kmer1) {
-                kmer1_id = present_nodes[&kmer1];
-            }
-            else {
-                kmer1_id = graph.add_node(kmer1.clone());
-                present_nodes.insert(kmer1, kmer1_id);
-            }
-    
-            // Process Kmer2
-            if present_nodes.contains_key(&kmer2) {
-                kmer2_id = present_nodes[&kmer2];
+        let mut rev_reads = Tile::new();
+        if self.both_strands {
+            reads.into_iter().for_each(|kmer| rev_reads.push(reverse_complement(kmer)));
+        }
+
+        // Iterate over all k-mers of reads and their complements.
+        // Counted k-mers are added once all reads are processed, in the order of their first occurrence.
+        let mut counts = HashMap::<Vec<u8>, usize>::new();
+        let mut counted = Vec::<Vec<u8>>::new();
+        for read in reads.into_iter().chain(&rev_reads) {
+            let read: Vec<u8> = match self.nucleotides_only {
+                true => read.into_iter().map(|x| x.to_ascii_uppercase()).collect(),
+                false => read.chain.clone(),
+            };
+            let k = self.k.unwrap_or(read.len());
+            if k < 2 || read.len() < k {
+                continue;
             }
-            else {
-                kmer2_id = graph.add_node(kmer2.clone());
-                present_nodes.insert(kmer2, kmer2_id);
+            for kmer in read.windows(k) {
+                if self.nucleotides_only && !kmer.iter().all(|&x| encode_base(x).is_some()) {
+                    continue;
+                }
+                if !self.counts {
+                    add_kmer(&mut graph, &mut present_nodes, kmer, None);
+                    continue;
+                }
+                let count = counts.entry(kmer.to_vec()).or_insert(0);
+                if *count == 0 {
+                    counted.push(kmer.to_vec());
+                }
+                *count += 1;
             }
-    
-            // Add edge connecting kmer1 with kmer2
-            graph.add_edge(&kmer1_id, &kmer2_id, None).unwrap();
+        }
+
+        for kmer in counted.iter().filter(|kmer| counts[*kmer] >= self.min_count) {
+            add_kmer(&mut graph, &mut present_nodes, kmer, Some(counts[kmer]));
         }
 
         graph
     }
 }
 
+// Add edge connecting prefix and suffix of a k-mer, creating nodes for (k-1)-mers not present yet
+fn add_kmer(graph: &mut Graph<Sequence, usize>, present_nodes: &mut HashMap<Sequence, u64>, kmer: &[u8], label: Option<usize>) {
+    let mut node_ids = [0_u64; 2];
+    for (node_id, node) in node_ids.iter_mut().zip([&kmer[0..kmer.len()-1], &kmer[1..kmer.len()]]) {
+        let node = Sequence::from(node);
+        *node_id = match present_nodes.get(&node) {
+            Some(&id) => id,
+            None => {
+                let id = graph.add_node(node.clone());
+                present_nodes.insert(node, id);
+                id
+            }
+        };
+    }
+    graph.add_edge(&node_ids[0], &node_ids[1], label).unwrap();
+}
+
 impl Default for DeBruijnBuilder {
     fn default() -> Self {
         Self::new()
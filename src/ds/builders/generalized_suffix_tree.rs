@@ -0,0 +1,552 @@
+use std::fmt;
+
+use crate::ds::algo::traversal::{DfsPostorder, DfsPreorder};
+use crate::ds::graph::{Graph, GraphProperties};
+use crate::ds::sequence::Sequence;
+use crate::ds::tile::Tile;
+
+use super::suffix_array::{SuffixArray, SuffixArrayBuilder, SENTINEL};
+
+/// Build a generalized suffix tree over all sequences of a tile
+///
+/// Complexity: O(n log n) time and space, with `n` - total length of the sequences
+///
+/// The tree is derived from the generalized suffix array and LCP array of `SuffixArrayBuilder`,
+/// every sequence being terminated by a distinct terminator. Compared to `SuffixTreeBuilder`,
+/// leaves are annotated with the sequence and offset of their suffix, and internal nodes with the number of
+/// occurrences and of distinct sequences containing their path label. Lowest common ancestors are answered in O(1)
+/// after preprocessing, which dominates the construction.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::builders::generalized_suffix_tree::GeneralizedSuffixTreeBuilder;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// let mut sequences = Tile::new();
+/// sequences.push(Sequence::from("GATTACA"));
+/// sequences.push(Sequence::from("TACAT"));
+/// let gst = GeneralizedSuffixTreeBuilder::new().build(&sequences);
+///
+/// assert!(gst.contains(b"ACAT"));
+/// assert_eq!(gst.count(b"TA"), 2);
+/// assert_eq!(gst.locate(b"CA"), vec![(0, 5), (1, 2)]);
+/// assert_eq!(gst.longest_common_substring(2), Some(Sequence::from("TACA")));
+/// assert_eq!(gst.longest_common_substring(1), Some(Sequence::from("TACA")));
+/// ```
+pub struct GeneralizedSuffixTreeBuilder {}
+
+impl GeneralizedSuffixTreeBuilder {
+
+    pub fn new() -> GeneralizedSuffixTreeBuilder {
+        GeneralizedSuffixTreeBuilder {}
+    }
+
+    pub fn build(&self, tile: &Tile) -> GeneralizedSuffixTree {
+        let sa = SuffixArrayBuilder::new().build_tile(tile);
+        let lcp = sa.lcp();
+        let n = sa.len();
+        let num_seqs = sa.offsets().len();
+
+        let mut graph = Graph::<SuffixNode, SuffixEdge>::new(GraphProperties{directed: true});
+        let root = graph.add_node(SuffixNode::new(0, 0, None));
+        graph.set_root(root);
+        let mut leaf_of = vec![root; n];
+
+        // Insert suffixes in lexicographic order, the stack holds the path from the root to the last leaf.
+        // Suffixes consisting of a terminator only are skipped, they occupy the first ranks.
+        let mut stack = vec![root];
+        for (rank, &pos) in sa.sa().iter().enumerate().skip(num_seqs) {
+            let shared = if rank == num_seqs { 0 } else { lcp[rank] };
+
+            // Ascend to the deepest node on the path, whose label is a prefix of the common prefix
+            let mut last = None;
+            while graph.get_node(stack.last().unwrap()).data.depth > shared {
+                last = stack.pop();
+            }
+            let parent = *stack.last().unwrap();
+            let parent_depth = graph.get_node(&parent).data.depth;
+
+            // Split the edge to the last child of the parent at the end of the common prefix
+            if parent_depth < shared {
+                let child = last.unwrap();
+                let edge = *graph.get_node(&parent).outgoing().last().unwrap();
+                graph.remove_edge(&edge);
+                let (child_start, child_depth) = {
+                    let data = &graph.get_node(&child).data;
+                    (data.start, data.depth)
+                };
+                let node = graph.add_node(SuffixNode::new(shared, pos, None));
+                graph.add_edge(&parent, &node, Some(SuffixEdge { start: pos + parent_depth, end: pos + shared })).unwrap();
+                graph.add_edge(&node, &child, Some(SuffixEdge { start: child_start + shared, end: child_start + child_depth })).unwrap();
+                stack.push(node);
+            }
+
+            // Leaf spells the suffix up to and including the terminator of its sequence
+            let parent = *stack.last().unwrap();
+            let parent_depth = graph.get_node(&parent).data.depth;
+            let (seq, offset) = sa.resolve(pos);
+            let end = sa.offsets().get(seq + 1).copied().unwrap_or(n);
+            let leaf = graph.add_node(SuffixNode::new(end - pos, pos, Some((seq, offset))));
+            graph.add_edge(&parent, &leaf, Some(SuffixEdge { start: pos + parent_depth, end })).unwrap();
+            leaf_of[pos] = leaf;
+            stack.push(leaf);
+        }
+
+        let mut gst = GeneralizedSuffixTree {
+            graph,
+            root,
+            sa,
+            leaf_of,
+            first: vec![],
+            tour: vec![],
+            sparse: vec![],
+            left: vec![],
+            ranks: vec![],
+        };
+        gst.preprocess_lca();
+        gst.annotate(num_seqs);
+        gst
+    }
+}
+
+impl Default for GeneralizedSuffixTreeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Node of a `GeneralizedSuffixTree`
+#[derive(Clone, Debug)]
+pub struct SuffixNode {
+    /// Length of the path label from the root, including the terminator for leaves
+    pub depth: usize,
+    /// Position of an occurrence of the path label within the text
+    pub start: usize,
+    /// Sequence index and offset of the suffix ending at a leaf, `None` for internal nodes
+    pub suffix: Option<(usize, usize)>,
+    /// Number of leaves below the node, i.e. occurrences of the path label
+    pub leaves: usize,
+    /// Number of distinct sequences containing the path label
+    pub sequences: usize,
+}
+
+impl SuffixNode {
+    fn new(depth: usize, start: usize, suffix: Option<(usize, usize)>) -> SuffixNode {
+        SuffixNode { depth, start, suffix, leaves: 0, sequences: 0 }
+    }
+}
+
+impl fmt::Display for SuffixNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.suffix {
+            Some((seq, offset)) => write!(f, "{}:{}", seq, offset),
+            None => write!(f, "{} ({}/{})", self.depth, self.leaves, self.sequences),
+        }
+    }
+}
+
+/// Edge of a `GeneralizedSuffixTree`, labelled with the interval `[start, end)` of the text
+#[derive(Clone, Debug)]
+pub struct SuffixEdge {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl fmt::Display for SuffixEdge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{},{})", self.start, self.end)
+    }
+}
+
+/// Substring occurring at least twice, see `GeneralizedSuffixTree::maximal_repeats`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repeat {
+    /// Repeated substring
+    pub sequence: Sequence,
+    /// Sequence index and offset of every occurrence, sorted
+    pub occurrences: Vec<(usize, usize)>,
+}
+
+/// Generalized suffix tree over multiple sequences, see `GeneralizedSuffixTreeBuilder`
+pub struct GeneralizedSuffixTree {
+    graph: Graph<SuffixNode, SuffixEdge>,
+    root: u64,
+    sa: SuffixArray,
+    // Leaf of the suffix starting at every position of the text, the root for terminators
+    leaf_of: Vec<u64>,
+    // Euler tour of the tree with the first position of every node on it,
+    // and sparse table of the tour positions of minimal depth within intervals of length 2^k
+    first: Vec<usize>,
+    tour: Vec<u64>,
+    sparse: Vec<Vec<u32>>,
+    // Letter preceding all occurrences of the path label of every node, `None` if they differ or start a sequence
+    left: Vec<Option<u8>>,
+    // Interval of suffix array ranks of the leaves below every node
+    ranks: Vec<(usize, usize)>,
+}
+
+impl GeneralizedSuffixTree {
+
+    /// Underlying tree, with edges leading from the root towards the leaves in lexicographic order
+    pub fn graph(&self) -> &Graph<SuffixNode, SuffixEdge> {
+        &self.graph
+    }
+
+    pub fn root(&self) -> u64 {
+        self.root
+    }
+
+    /// Concatenation of the indexed sequences, each terminated by `SENTINEL`
+    pub fn text(&self) -> &[u8] {
+        self.sa.text()
+    }
+
+    /// Path label of a node, without the terminator of leaves
+    pub fn label(&self, node: u64) -> Sequence {
+        let data = &self.graph.get_node(&node).data;
+        let mut label = &self.text()[data.start..data.start + data.depth];
+        if data.suffix.is_some() {
+            label = &label[..label.len() - 1];
+        }
+        Sequence::from(label)
+    }
+
+    /// Leaf of the suffix of sequence `seq` starting at `offset`, `None` if out of bounds
+    pub fn leaf(&self, seq: usize, offset: usize) -> Option<u64> {
+        let pos = self.sa.offsets().get(seq)? + offset;
+        let end = self.sa.offsets().get(seq + 1).copied().unwrap_or(self.sa.len());
+        if pos + 1 < end { Some(self.leaf_of[pos]) } else { None }
+    }
+
+    /// Highest node, whose path label starts with `pattern`, `None` if `pattern` does not occur
+    pub fn locus(&self, pattern: &[u8]) -> Option<u64> {
+        if pattern.contains(&SENTINEL) {
+            return None;
+        }
+        let text = self.text();
+        let mut node = self.root;
+        let mut matched = 0;
+        while matched < pattern.len() {
+            let edge = self.graph.out_edges(node)
+                .map(|id| self.graph.get_edge(id))
+                .find(|edge| text[edge.data.as_ref().unwrap().start] == pattern[matched])?;
+            let label = edge.data.as_ref().unwrap();
+            for &c in &text[label.start..label.end] {
+                if matched == pattern.len() {
+                    break;
+                }
+                if c != pattern[matched] {
+                    return None;
+                }
+                matched += 1;
+            }
+            node = edge.end;
+        }
+        Some(node)
+    }
+
+    /// Whether `pattern` occurs in any of the sequences
+    ///
+    /// Complexity: O(m σ), with `m` - length of the pattern, `σ` - size of the alphabet
+    pub fn contains(&self, pattern: &[u8]) -> bool {
+        self.locus(pattern).is_some()
+    }
+
+    /// Number of occurrences of `pattern` within all sequences
+    ///
+    /// Complexity: O(m σ)
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        self.locus(pattern).map_or(0, |node| self.graph.get_node(&node).data.leaves)
+    }
+
+    /// Sequence index and offset of every occurrence of `pattern`, sorted
+    ///
+    /// Complexity: O(m σ + k log k), with `k` - number of occurrences
+    pub fn locate(&self, pattern: &[u8]) -> Vec<(usize, usize)> {
+        match self.locus(pattern) {
+            Some(node) => self.occurrences(node),
+            None => vec![],
+        }
+    }
+
+    /// Lowest common ancestor of two nodes, whose path label is the longest common prefix of their labels
+    ///
+    /// Complexity: O(1)
+    pub fn lca(&self, a: u64, b: u64) -> u64 {
+        let (mut i, mut j) = (self.first[a as usize], self.first[b as usize]);
+        if i > j {
+            std::mem::swap(&mut i, &mut j);
+        }
+        let k = (j - i + 1).ilog2() as usize;
+        let x = self.sparse[k][i] as usize;
+        let y = self.sparse[k][j + 1 - (1 << k)] as usize;
+        if self.tour_depth(x) <= self.tour_depth(y) { self.tour[x] } else { self.tour[y] }
+    }
+
+    /// Length of the longest common prefix of the suffixes of sequence `seq1` at `pos1` and of sequence `seq2` at `pos2`
+    ///
+    /// Complexity: O(1)
+    ///
+    /// Known as longest common extension, positions at or beyond the end of a sequence have an extension of 0.
+    pub fn lce(&self, seq1: usize, pos1: usize, seq2: usize, pos2: usize) -> usize {
+        let (Some(a), Some(b)) = (self.leaf(seq1, pos1), self.leaf(seq2, pos2)) else {
+            return 0;
+        };
+        if a == b {
+            return self.graph.get_node(&a).data.depth - 1;
+        }
+        self.graph.get_node(&self.lca(a, b)).data.depth
+    }
+
+    /// Longest substring occurring in at least `min_sequences` of the sequences, `None` if there is none
+    ///
+    /// Complexity: O(n)
+    ///
+    /// Among substrings of equal length, the lexicographically smallest one is returned.
+    /// Values of `min_sequences` below 2 are treated as 2.
+    pub fn longest_common_substring(&self, min_sequences: usize) -> Option<Sequence> {
+        let min_sequences = min_sequences.max(2);
+        let mut best: Option<(usize, u64)> = None;
+        for node in self.internal_nodes() {
+            let data = &self.graph.get_node(&node).data;
+            if data.sequences >= min_sequences && best.is_none_or(|(depth, _)| data.depth > depth) {
+                best = Some((data.depth, node));
+            }
+        }
+        best.map(|(_, node)| self.label(node))
+    }
+
+    /// Maximal substrings occurring in at least `min_sequences` of the sequences, in lexicographic order
+    ///
+    /// Complexity: O(n)
+    ///
+    /// Substrings are maximal, if they cannot be extended by a letter on either side, while still occurring in
+    /// `min_sequences` sequences. Every substring shared by that many sequences is contained in one of them.
+    /// Values of `min_sequences` below 2 are treated as 2.
+    ///
+    /// # Arguments
+    /// * `min_sequences` - minimal number of distinct sequences containing a substring
+    /// * `min_len` - minimal length of reported substrings
+    pub fn common_substrings(&self, min_sequences: usize, min_len: usize) -> Vec<Sequence> {
+        let min_sequences = min_sequences.max(2);
+        let shared = |node: u64| self.graph.get_node(&node).data.sequences >= min_sequences;
+
+        // Node extendable to the left, if the suffix link of a shared node points to it
+        let mut extendable = vec![false; self.graph.node_bound()];
+        for node in self.internal_nodes().filter(|&node| shared(node)) {
+            extendable[self.suffix_link(node) as usize] = true;
+        }
+
+        // Nodes without shared children cannot be extended to the right
+        self.internal_nodes()
+            .filter(|&node| shared(node) && !extendable[node as usize])
+            .filter(|&node| self.graph.get_node(&node).data.depth >= min_len)
+            .filter(|&node| !self.graph.out_neighbors(node).any(|&child| shared(child)))
+            .map(|node| self.label(node))
+            .collect()
+    }
+
+    /// Maximal repeats, which occur at least twice and cannot be extended on either side without losing an occurrence
+    ///
+    /// Complexity: O(n + k log k), with `k` - total number of occurrences of reported repeats
+    ///
+    /// Repeats are reported in lexicographic order, occurrences may lie in the same or in different sequences.
+    ///
+    /// # Arguments
+    /// * `min_len` - minimal length of reported repeats
+    ///
+    /// # Example
+    /// ```
+    /// use biogarden::ds::builders::generalized_suffix_tree::GeneralizedSuffixTreeBuilder;
+    /// use biogarden::ds::sequence::Sequence;
+    /// use biogarden::ds::tile::Tile;
+    ///
+    /// let mut sequences = Tile::new();
+    /// sequences.push(Sequence::from("ACGTTACGA"));
+    /// let gst = GeneralizedSuffixTreeBuilder::new().build(&sequences);
+    ///
+    /// let repeats = gst.maximal_repeats(2);
+    /// assert_eq!(repeats.len(), 1);
+    /// assert_eq!(repeats[0].sequence, Sequence::from("ACG"));
+    /// assert_eq!(repeats[0].occurrences, vec![(0, 0), (0, 5)]);
+    /// ```
+    pub fn maximal_repeats(&self, min_len: usize) -> Vec<Repeat> {
+        // Internal nodes are right-maximal, as their label is followed by at least two different letters
+        self.internal_nodes()
+            .filter(|&node| self.left[node as usize].is_none())
+            .filter(|&node| self.graph.get_node(&node).data.depth >= min_len)
+            .map(|node| Repeat { sequence: self.label(node), occurrences: self.occurrences(node) })
+            .collect()
+    }
+
+    /// Supermaximal repeats, i.e. maximal repeats that are not contained in any other maximal repeat
+    ///
+    /// Complexity: O(n + k log k)
+    ///
+    /// # Arguments
+    /// * `min_len` - minimal length of reported repeats
+    ///
+    /// # Example
+    /// ```
+    /// use biogarden::ds::builders::generalized_suffix_tree::GeneralizedSuffixTreeBuilder;
+    /// use biogarden::ds::sequence::Sequence;
+    /// use biogarden::ds::tile::Tile;
+    ///
+    /// let mut sequences = Tile::new();
+    /// sequences.push(Sequence::from("GAATTCGAATTCAGAA"));
+    /// let gst = GeneralizedSuffixTreeBuilder::new().build(&sequences);
+    ///
+    /// // Maximal repeat GAA is contained in GAATTC
+    /// let maximal: Vec<Sequence> = gst.maximal_repeats(3).into_iter().map(|r| r.sequence).collect();
+    /// assert_eq!(maximal, vec![Sequence::from("GAA"), Sequence::from("GAATTC")]);
+    /// let supermaximal: Vec<Sequence> = gst.supermaximal_repeats(3).into_iter().map(|r| r.sequence).collect();
+    /// assert_eq!(supermaximal, vec![Sequence::from("GAATTC")]);
+    /// ```
+    pub fn supermaximal_repeats(&self, min_len: usize) -> Vec<Repeat> {
+        // All children are leaves, preceded by pairwise distinct letters
+        let supermaximal = |node: u64| {
+            let mut seen = [false; 256];
+            self.graph.out_neighbors(node).all(|&child| {
+                if self.graph.get_node(&child).data.suffix.is_none() {
+                    return false;
+                }
+                match self.left[child as usize] {
+                    Some(c) => !std::mem::replace(&mut seen[c as usize], true),
+                    None => true,
+                }
+            })
+        };
+        self.internal_nodes()
+            .filter(|&node| self.graph.get_node(&node).data.depth >= min_len)
+            .filter(|&node| supermaximal(node))
+            .map(|node| Repeat { sequence: self.label(node), occurrences: self.occurrences(node) })
+            .collect()
+    }
+
+    // Internal nodes except for the root, in lexicographic order of their labels
+    fn internal_nodes(&self) -> impl Iterator<Item = u64> + '_ {
+        DfsPreorder::new(&self.graph, self.root)
+            .unwrap()
+            .filter(move |&node| node != self.root && self.graph.get_node(&node).data.suffix.is_none())
+    }
+
+    // Sequence index and offset of the suffixes at all leaves below `node`, sorted
+    fn occurrences(&self, node: u64) -> Vec<(usize, usize)> {
+        let (lo, hi) = self.ranks[node as usize];
+        let mut occurrences: Vec<(usize, usize)> = self.sa.sa()[lo..hi].iter().map(|&pos| self.sa.resolve(pos)).collect();
+        occurrences.sort_unstable();
+        occurrences
+    }
+
+    // Node labelled with the label of an internal node without its first letter.
+    // Leaves of suffixes starting in different subtrees of the node share exactly its label,
+    // the suffixes starting one position later share its label without the first letter.
+    fn suffix_link(&self, node: u64) -> u64 {
+        let children: Vec<u64> = self.graph.out_neighbors(node).cloned().collect();
+        let leaf_start = |mut id: u64| {
+            while self.graph.get_node(&id).data.suffix.is_none() {
+                id = self.graph.get_edge(&self.graph.get_node(&id).outgoing()[0]).end;
+            }
+            self.graph.get_node(&id).data.start
+        };
+        let (a, b) = (leaf_start(children[0]), leaf_start(children[1]));
+        self.lca(self.leaf_of[a + 1], self.leaf_of[b + 1])
+    }
+
+    fn tour_depth(&self, i: usize) -> usize {
+        self.graph.get_node(&self.tour[i]).data.depth
+    }
+
+    // Record the Euler tour of the tree and build the sparse table for range minimum queries on it
+    fn preprocess_lca(&mut self) {
+        self.first = vec![0; self.graph.node_bound()];
+        self.tour = vec![self.root];
+        let mut stack = vec![(self.root, 0)];
+        while let Some((node, next)) = stack.last_mut() {
+            let outgoing = self.graph.get_node(node).outgoing();
+            if *next < outgoing.len() {
+                let child = self.graph.get_edge(&outgoing[*next]).end;
+                *next += 1;
+                self.first[child as usize] = self.tour.len();
+                self.tour.push(child);
+                stack.push((child, 0));
+            } else {
+                stack.pop();
+                if let Some((parent, _)) = stack.last() {
+                    self.tour.push(*parent);
+                }
+            }
+        }
+
+        self.sparse = vec![(0..self.tour.len() as u32).collect()];
+        let mut len = 1;
+        while 2 * len <= self.tour.len() {
+            let prev = self.sparse.last().unwrap();
+            let level: Vec<u32> = (0..=self.tour.len() - 2 * len)
+                .map(|i| {
+                    let (x, y) = (prev[i], prev[i + len]);
+                    if self.tour_depth(x as usize) <= self.tour_depth(y as usize) { x } else { y }
+                })
+                .collect();
+            self.sparse.push(level);
+            len *= 2;
+        }
+    }
+
+    // Count leaves and distinct sequences below every node, and determine the letters preceding their labels
+    fn annotate(&mut self, num_seqs: usize) {
+        let bound = self.graph.node_bound();
+        let text = self.sa.text();
+
+        // Consecutive leaves of the same sequence in lexicographic order are counted twice at their lowest common
+        // ancestor and all nodes above it, all other nodes with leaves of the sequence below are counted once
+        let mut duplicates = vec![0_usize; bound];
+        let mut previous: Vec<Option<u64>> = vec![None; num_seqs];
+        let mut ranks = vec![(0, 0); bound];
+        for (rank, &pos) in self.sa.sa().iter().enumerate().skip(num_seqs) {
+            let leaf = self.leaf_of[pos];
+            ranks[leaf as usize] = (rank, rank + 1);
+            let (seq, _) = self.graph.get_node(&leaf).data.suffix.unwrap();
+            if let Some(prev) = previous[seq] {
+                duplicates[self.lca(prev, leaf) as usize] += 1;
+            }
+            previous[seq] = Some(leaf);
+        }
+
+        let mut leaves = vec![0_usize; bound];
+        let mut left: Vec<Option<u8>> = vec![None; bound];
+        let order: Vec<u64> = DfsPostorder::new(&self.graph, self.root).unwrap().collect();
+        for &node in &order {
+            let v = node as usize;
+            match self.graph.get_node(&node).data.suffix {
+                Some((_, offset)) => {
+                    let start = self.graph.get_node(&node).data.start;
+                    leaves[v] = 1;
+                    left[v] = if offset == 0 { None } else { Some(text[start - 1]) };
+                }
+                None => {
+                    let children: Vec<usize> = self.graph.out_neighbors(node).map(|&c| c as usize).collect();
+                    if children.is_empty() {
+                        continue;
+                    }
+                    ranks[v] = (ranks[children[0]].0, ranks[*children.last().unwrap()].1);
+                    for &c in &children {
+                        leaves[v] += leaves[c];
+                        duplicates[v] += duplicates[c];
+                    }
+                    let first = left[children[0]];
+                    left[v] = if children.iter().all(|&c| left[c].is_some() && left[c] == first) { first } else { None };
+                }
+            }
+        }
+
+        for &node in &order {
+            let data = &mut self.graph.get_node_mut(&node).data;
+            data.leaves = leaves[node as usize];
+            data.sequences = leaves[node as usize] - duplicates[node as usize];
+        }
+        self.left = left;
+        self.ranks = ranks;
+    }
+}
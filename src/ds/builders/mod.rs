@@ -3,4 +3,10 @@ pub mod suffix_tree;
 /// Trie builder
 pub mod trie;
 /// DEBruijn-Graph builder
-pub mod debruijn;
\ No newline at end of file
+pub mod debruijn;
+/// Suffix array, LCP array and Burrows-Wheeler transform builder
+pub mod suffix_array;
+/// Overlap graph and string graph builder
+pub mod string_graph;
+/// Generalized suffix tree over multiple sequences
+pub mod generalized_suffix_tree;
@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+
+use crate::alignment::aligner::SequenceAligner;
+use crate::alignment::score::unit;
+use crate::ds::graph::Graph;
+use crate::ds::graph::GraphProperties;
+use crate::ds::sequence::{Sequence, Strand};
+use crate::ds::tile::Tile;
+use crate::error::{BioError, Result};
+use crate::processing::kmers::encode_base;
+use crate::processing::transformers::reverse_complement;
+
+/// Approximate suffix-prefix overlap of two reads, found by `StringGraphBuilder`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlap {
+    /// Index of the read, whose suffix overlaps
+    pub from: usize,
+    /// Orientation of the `from` read
+    pub from_strand: Strand,
+    /// Index of the read, whose prefix overlaps
+    pub to: usize,
+    /// Orientation of the `to` read
+    pub to_strand: Strand,
+    /// Length of the overlapping suffix of `from`
+    pub from_len: usize,
+    /// Length of the overlapping prefix of `to`
+    pub to_len: usize,
+    /// Number of mismatches and gaps within the alignment of the overlap
+    pub errors: usize,
+}
+
+/// Build overlap graphs and string graphs for overlap-layout-consensus assembly
+///
+/// Reads are connected by maximal suffix-prefix overlaps of at least `min_overlap` bases,
+/// tolerating mismatches and gaps up to `max_error_rate` of the overlap alignment.
+/// Candidate pairs are located by exact seeds of the read prefixes, avoiding the comparison of all pairs of reads,
+/// and verified by `SequenceAligner::overlap_alignment`. Reads contained within another read are discarded.
+/// By default, Myers' transitive reduction is applied, yielding a string graph, in which overlaps implied by
+/// two shorter overlaps are removed. Optionally, reverse complements of the reads are included, such that
+/// overlaps between reads from opposite strands are detected, each orientation represented by a separate node.
+///
+/// Nodes contain the (uppercase) reads, reverse complements named by the read identifier suffixed with `-`,
+/// edges are labelled with the length of the overlapping suffix of the source read.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::builders::string_graph::StringGraphBuilder;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// // Consecutive reads of GATTACACCGTAGGCTTAACGGATCCAGTTCAGG, the second one with a sequencing error
+/// let mut reads = Tile::new();
+/// reads.push(Sequence::from("GATTACACCGTAGGCTTAACGG"));
+/// reads.push(Sequence::from("ACCGTAGGCATAACGGATCC"));
+/// reads.push(Sequence::from("GGCTTAACGGATCCAGTTCAGG"));
+///
+/// let builder = StringGraphBuilder::new().with_min_overlap(10).with_seed_len(6).with_max_error_rate(0.1);
+/// let overlaps = builder.overlaps(&reads).unwrap();
+/// assert_eq!(overlaps.len(), 3);
+///
+/// // Overlap of the first and the third read is implied by the overlaps with the second read
+/// let graph = builder.build(&reads).unwrap();
+/// assert_eq!((graph.node_count(), graph.edge_count()), (3, 2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StringGraphBuilder {
+    min_overlap: usize,
+    max_error_rate: f64,
+    seed_len: usize,
+    both_strands: bool,
+    reduce: bool,
+    fuzz: usize,
+}
+
+impl StringGraphBuilder {
+
+    pub fn new() -> StringGraphBuilder {
+        StringGraphBuilder {
+            min_overlap: 20,
+            max_error_rate: 0.05,
+            seed_len: 12,
+            both_strands: false,
+            reduce: true,
+            fuzz: 10,
+        }
+    }
+
+    /// Minimum length of an overlap, on both reads
+    pub fn with_min_overlap(mut self, min_overlap: usize) -> Self {
+        self.min_overlap = min_overlap;
+        self
+    }
+
+    /// Maximum fraction of mismatches and gaps within the alignment of an overlap
+    pub fn with_max_error_rate(mut self, max_error_rate: f64) -> Self {
+        self.max_error_rate = max_error_rate;
+        self
+    }
+
+    /// Length of the exact seeds used to locate candidate overlaps, at most `min_overlap`
+    ///
+    /// Overlaps are only detected, if the first `min_overlap` bases of the overlap share a seed.
+    pub fn with_seed_len(mut self, seed_len: usize) -> Self {
+        self.seed_len = seed_len;
+        self
+    }
+
+    /// Include reverse complements of the reads, see `processing::transformers::reverse_complement`
+    pub fn with_both_strands(mut self, both_strands: bool) -> Self {
+        self.both_strands = both_strands;
+        self
+    }
+
+    /// Apply transitive reduction to the overlap graph, see `transitive_reduction`
+    pub fn with_transitive_reduction(mut self, reduce: bool) -> Self {
+        self.reduce = reduce;
+        self
+    }
+
+    /// Tolerance in bases when comparing overlaps during transitive reduction
+    pub fn with_fuzz(mut self, fuzz: usize) -> Self {
+        self.fuzz = fuzz;
+        self
+    }
+
+    /// Find all overlaps between reads, not including reads contained within other reads
+    ///
+    /// Overlaps are sorted by the index and orientation of their reads.
+    /// Fails, if the seed length is zero or exceeds the minimum overlap, or the error rate is not within `[0, 1)`.
+    pub fn overlaps(&self, reads: &Tile) -> Result<Vec<Overlap>> {
+        Ok(self.compute_overlaps(reads)?.0)
+    }
+
+    /// Build the overlap graph of the reads, or the string graph if transitive reduction is enabled
+    pub fn build(&self, reads: &Tile) -> Result<Graph<Sequence, usize>> {
+
+        let (overlaps, contained) = self.compute_overlaps(reads)?;
+        let mut graph = Graph::<Sequence, usize>::new(GraphProperties{directed: true});
+
+        // Add a node for every orientation of the reads, which are not contained in other reads
+        let mut node_ids = HashMap::<(usize, Strand), u64>::new();
+        for (i, read) in reads.into_iter().enumerate().filter(|(i, _)| !contained[*i]) {
+            let read = uppercase(read);
+            if self.both_strands {
+                let mut complement = reverse_complement(&read);
+                complement.id = read.id.as_ref().map(|id| format!("{}-", id));
+                node_ids.insert((i, Strand::Reverse), graph.add_node(complement));
+            }
+            node_ids.insert((i, Strand::Forward), graph.add_node(read));
+        }
+
+        // Connect reads by their overlaps, labelled with the overlap length of the source read
+        for overlap in &overlaps {
+            let from = node_ids[&(overlap.from, overlap.from_strand)];
+            let to = node_ids[&(overlap.to, overlap.to_strand)];
+            graph.add_edge(&from, &to, Some(overlap.from_len)).unwrap();
+        }
+
+        if self.reduce {
+            transitive_reduction(&mut graph, self.fuzz);
+        }
+        Ok(graph)
+    }
+
+    // Overlaps between non-contained reads, and flags marking the contained reads
+    fn compute_overlaps(&self, reads: &Tile) -> Result<(Vec<Overlap>, Vec<bool>)> {
+
+        if self.seed_len == 0 || self.seed_len > self.min_overlap {
+            return Err(BioError::InvalidArgumentRange);
+        }
+        if !(0.0..1.0).contains(&self.max_error_rate) {
+            return Err(BioError::InvalidArgumentRange);
+        }
+
+        // Orientations of the reads, ordered by read index
+        let mut oriented = Vec::<(usize, Strand, Sequence)>::new();
+        for (i, read) in reads.into_iter().enumerate() {
+            let read = uppercase(read);
+            if self.both_strands {
+                oriented.push((i, Strand::Reverse, reverse_complement(&read)));
+            }
+            oriented.push((i, Strand::Forward, read));
+        }
+        oriented.sort_by_key(|&(i, strand, _)| (i, strand == Strand::Reverse));
+
+        // Index seeds within the first `min_overlap` bases of every read, to which any overlap must extend
+        let mut index = HashMap::<&[u8], Vec<(usize, usize)>>::new();
+        for (o, (_, _, read)) in oriented.iter().enumerate() {
+            let end = self.min_overlap.min(read.len());
+            for (offset, seed) in read.chain[..end].windows(self.seed_len).enumerate() {
+                if seed.iter().all(|&x| encode_base(x).is_some()) {
+                    index.entry(seed).or_default().push((o, offset));
+                }
+            }
+        }
+
+        let mut aligner = SequenceAligner::new();
+        let mut candidates = Vec::<(usize, usize, usize)>::new();
+        let mut contained = vec![false; reads.len()];
+        for (a, (read_a, _, seq_a)) in oriented.iter().enumerate() {
+
+            // Vote for the start of other reads within `seq_a`, given by the diagonal of the shared seeds
+            let mut votes = HashMap::<(usize, usize), usize>::new();
+            for (pos, seed) in seq_a.chain.windows(self.seed_len).enumerate() {
+                for &(b, offset) in index.get(seed).into_iter().flatten() {
+                    if oriented[b].0 != *read_a && pos >= offset {
+                        *votes.entry((b, pos - offset)).or_insert(0) += 1;
+                    }
+                }
+            }
+            // Keep the best supported diagonal for every read
+            let mut best = HashMap::<usize, (usize, usize)>::new();
+            for (&(b, diagonal), &count) in &votes {
+                let entry = best.entry(b).or_insert((diagonal, count));
+                if (count, std::cmp::Reverse(diagonal)) > (entry.1, std::cmp::Reverse(entry.0)) {
+                    *entry = (diagonal, count);
+                }
+            }
+            for (b, (diagonal, _)) in best {
+                let seq_b = &oriented[b].2;
+                if diagonal + seq_b.len() <= seq_a.len() {
+                    // Candidate `seq_b` lies within `seq_a`, ties between equal reads are broken by index
+                    let (read_b, _, _) = oriented[b];
+                    if (seq_b.len() < seq_a.len() || read_b > *read_a) && self.contains(&mut aligner, seq_a, seq_b, diagonal) {
+                        contained[read_b] = true;
+                    }
+                } else {
+                    candidates.push((a, b, diagonal));
+                }
+            }
+        }
+
+        // Verify candidate overlaps of non-contained reads by alignment
+        let mut overlaps = Vec::<Overlap>::new();
+        for (a, b, diagonal) in candidates {
+            let (read_a, strand_a, seq_a) = &oriented[a];
+            let (read_b, strand_b, seq_b) = &oriented[b];
+            if contained[*read_a] || contained[*read_b] {
+                continue;
+            }
+            if let Some((from_len, to_len, errors)) = self.align_overlap(&mut aligner, seq_a, seq_b, diagonal) {
+                overlaps.push(Overlap {
+                    from: *read_a, from_strand: *strand_a,
+                    to: *read_b, to_strand: *strand_b,
+                    from_len, to_len, errors,
+                });
+            }
+        }
+        overlaps.sort_by_key(|o| (o.from, o.from_strand == Strand::Reverse, o.to, o.to_strand == Strand::Reverse));
+
+        Ok((overlaps, contained))
+    }
+
+    // Number of bases by which an alignment may deviate from the diagonal of its seeds
+    fn slack(&self, len: usize) -> usize {
+        (len as f64 * self.max_error_rate).ceil() as usize + 1
+    }
+
+    // Check whether `seq_b` approximately occurs in `seq_a`, starting around `diagonal`
+    fn contains(&self, aligner: &mut SequenceAligner, seq_a: &Sequence, seq_b: &Sequence, diagonal: usize) -> bool {
+        let slack = self.slack(seq_b.len());
+        let window = Sequence::from(&seq_a[diagonal.saturating_sub(slack)..seq_a.len().min(diagonal + seq_b.len() + slack)]);
+        let (_, _, s1_aligned, s2_aligned) = aligner.banded_fitting_alignment(&window, seq_b, &unit, -2, -1, slack).unwrap();
+        errors(&s1_aligned, &s2_aligned) as f64 <= self.max_error_rate * s1_aligned.len() as f64
+    }
+
+    // Align the suffix of `seq_a` starting around `diagonal` with the prefix of `seq_b`,
+    // returning the overlap lengths and the number of errors if the overlap is sufficiently long and similar
+    fn align_overlap(&self, aligner: &mut SequenceAligner, seq_a: &Sequence, seq_b: &Sequence, diagonal: usize)
+                        -> Option<(usize, usize, usize)> {
+
+        let slack = self.slack(seq_a.len() - diagonal);
+        let suffix = Sequence::from(&seq_a[diagonal.saturating_sub(slack)..]);
+        let prefix = Sequence::from(&seq_b[..seq_b.len().min(seq_a.len() - diagonal + slack)]);
+        let (_, s1_aligned, s2_aligned) = aligner.overlap_alignment(&suffix, &prefix, &unit, -2, -1).unwrap();
+
+        let from_len = s1_aligned.chain.iter().filter(|&&x| x != b'-').count();
+        let to_len = s2_aligned.chain.iter().filter(|&&x| x != b'-').count();
+        let errors = errors(&s1_aligned, &s2_aligned);
+
+        // Overlap must leave an overhang on both reads, otherwise one read is contained in the other
+        let proper = from_len < seq_a.len() && to_len < seq_b.len();
+        let long = from_len >= self.min_overlap && to_len >= self.min_overlap;
+        let similar = errors as f64 <= self.max_error_rate * s1_aligned.len() as f64;
+        (proper && long && similar).then_some((from_len, to_len, errors))
+    }
+}
+
+impl Default for StringGraphBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Remove transitive edges from an overlap graph (Myers, 2005)
+///
+/// An edge `v -> x` is transitive, if there are edges `v -> w` and `w -> x`, such that the overlap of `v` and `x`
+/// is implied by the overlaps with `w`. The length of an edge is the number of bases of the source read
+/// preceding the overlap, edges are compared allowing for differences up to `fuzz` bases.
+/// Returns the number of removed edges.
+///
+/// # Arguments
+/// * `graph` - overlap graph with reads as nodes, edges labelled with the overlap length on the source read
+/// * `fuzz` - tolerance in bases, accounting for sequencing errors
+///
+/// # Example
+/// ```
+/// use biogarden::ds::builders::string_graph::transitive_reduction;
+/// use biogarden::ds::graph::{Graph, GraphProperties};
+/// use biogarden::ds::sequence::Sequence;
+///
+/// // Reads at offsets 0, 4 and 7 of a genome
+/// let mut graph = Graph::<Sequence, usize>::new(GraphProperties{directed: true});
+/// let a = graph.add_node(Sequence::from("GATTACACCG"));
+/// let b = graph.add_node(Sequence::from("ACACCGTAGG"));
+/// let c = graph.add_node(Sequence::from("CCGTAGGCTT"));
+/// graph.add_edge(&a, &b, Some(6)).unwrap();
+/// graph.add_edge(&b, &c, Some(7)).unwrap();
+/// graph.add_edge(&a, &c, Some(3)).unwrap();
+///
+/// assert_eq!(transitive_reduction(&mut graph, 0), 1);
+/// assert!(graph.has_edge(&a, &c).is_err());
+/// ```
+pub fn transitive_reduction(graph: &mut Graph<Sequence, usize>, fuzz: usize) -> usize {
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark { Vacant, InPlay, Eliminated }
+
+    // Outgoing edges with their target and length, ordered by length
+    let out_edges = |graph: &Graph<Sequence, usize>, v: u64| -> Vec<(u64, u64, usize)> {
+        let len = graph.get_node(&v).data.len();
+        let mut out: Vec<(u64, u64, usize)> = graph.get_node(&v).outgoing().iter()
+            .map(|&e| {
+                let edge = graph.get_edge(&e);
+                (e, edge.end, len.saturating_sub(edge.data.unwrap_or(0)))
+            })
+            .collect();
+        out.sort_by_key(|&(e, _, len)| (len, e));
+        out
+    };
+
+    let mut nodes: Vec<u64> = graph.nodes().cloned().collect();
+    nodes.sort_unstable();
+    let mut mark: HashMap<u64, Mark> = nodes.iter().map(|&v| (v, Mark::Vacant)).collect();
+
+    let mut transitive = Vec::<u64>::new();
+    for &v in &nodes {
+        let out_v = out_edges(graph, v);
+        let Some(&(_, _, longest)) = out_v.last() else {
+            continue;
+        };
+        let longest = longest + fuzz;
+        for &(_, w, _) in &out_v {
+            mark.insert(w, Mark::InPlay);
+        }
+
+        // Eliminate targets reachable through a shorter edge, with a combined length matching the direct edge
+        for &(_, w, len_vw) in &out_v {
+            if mark[&w] != Mark::InPlay {
+                continue;
+            }
+            for (_, x, len_wx) in out_edges(graph, w) {
+                if len_vw + len_wx > longest {
+                    break;
+                }
+                if mark[&x] == Mark::InPlay {
+                    mark.insert(x, Mark::Eliminated);
+                }
+            }
+        }
+        // Eliminate targets adjacent to the shortest edges of the successors, accounting for errors in edge lengths
+        for &(_, w, _) in &out_v {
+            for (i, (_, x, len_wx)) in out_edges(graph, w).into_iter().enumerate() {
+                if i > 0 && len_wx >= fuzz {
+                    break;
+                }
+                if mark[&x] == Mark::InPlay {
+                    mark.insert(x, Mark::Eliminated);
+                }
+            }
+        }
+
+        for &(e, w, _) in &out_v {
+            if mark[&w] == Mark::Eliminated {
+                transitive.push(e);
+            }
+            mark.insert(w, Mark::Vacant);
+        }
+    }
+
+    for e in &transitive {
+        graph.remove_edge(e);
+    }
+    transitive.len()
+}
+
+fn uppercase(read: &Sequence) -> Sequence {
+    let mut read = read.clone();
+    read.chain.make_ascii_uppercase();
+    read
+}
+
+// Number of alignment columns with differing letters, including gaps
+fn errors(s1_aligned: &Sequence, s2_aligned: &Sequence) -> usize {
+    s1_aligned.into_iter().zip(s2_aligned).filter(|(x, y)| x != y).count()
+}
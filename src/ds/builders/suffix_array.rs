@@ -0,0 +1,363 @@
+use crate::ds::sequence::Sequence;
+use crate::ds::tile::Tile;
+use crate::error::{BioError, Result};
+
+/// Terminator appended to every indexed sequence, sorts before all other letters
+pub const SENTINEL: u8 = b'$';
+
+// Marker for unfilled slots of the suffix array during induced sorting
+const EMPTY: usize = usize::MAX;
+
+/// Build suffix array in O(n) using the SA-IS algorithm (induced sorting)
+///
+/// Compared to `SuffixTreeBuilder`, the suffix array stores a single integer per position of the text,
+/// making it suitable for genome-sized inputs. LCP array and Burrows-Wheeler transform are derived from it.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::builders::suffix_array::SuffixArrayBuilder;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// let seq = Sequence::from("GATTACA");
+/// let sa = SuffixArrayBuilder::new().build(&seq);
+///
+/// assert_eq!(sa.sa(), [7, 6, 4, 1, 5, 0, 3, 2]);
+/// assert_eq!(sa.lcp(), [0, 0, 1, 1, 0, 0, 0, 1]);
+/// assert_eq!(sa.bwt(), b"ACTGA$TA");
+/// ```
+pub struct SuffixArrayBuilder {}
+
+impl SuffixArrayBuilder {
+
+    pub fn new() -> SuffixArrayBuilder {
+        SuffixArrayBuilder {}
+    }
+
+    /// Build the suffix array of a single sequence terminated by `SENTINEL`
+    pub fn build(&self, seq: &Sequence) -> SuffixArray {
+        let mut tile = Tile::new();
+        tile.push(seq.clone());
+        self.build_tile(&tile)
+    }
+
+    /// Build the generalized suffix array of all sequences in a tile
+    ///
+    /// Sequences are concatenated, each one terminated by `SENTINEL`.
+    /// Terminators are ranked as distinct letters (ordered by reversed sequence index),
+    /// such that no common prefix of two suffixes extends beyond the end of a sequence.
+    pub fn build_tile(&self, tile: &Tile) -> SuffixArray {
+        let num_seqs = tile.len();
+
+        // Concatenate sequences, while mapping text into integer alphabet for SA-IS:
+        // terminator of sequence `i` is ranked `num_seqs - 1 - i`, letters are shifted by `num_seqs`
+        let mut text = Vec::<u8>::new();
+        let mut ranks = Vec::<usize>::new();
+        let mut offsets = Vec::<usize>::new();
+        for (i, seq) in tile.into_iter().enumerate() {
+            offsets.push(text.len());
+            text.extend(seq);
+            ranks.extend(seq.into_iter().map(|&c| c as usize + num_seqs));
+            text.push(SENTINEL);
+            ranks.push(num_seqs - 1 - i);
+        }
+
+        let sa = sais(&ranks, num_seqs + 256);
+        SuffixArray { text, sa, offsets }
+    }
+}
+
+impl Default for SuffixArrayBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Suffix array over one or more sequences, see `SuffixArrayBuilder`
+#[derive(Clone, Debug)]
+pub struct SuffixArray {
+    // Concatenation of indexed sequences, each terminated by `SENTINEL`
+    text: Vec<u8>,
+    // Starting positions of suffixes in lexicographic order
+    sa: Vec<usize>,
+    // Starting position of every indexed sequence within `text`
+    offsets: Vec<usize>,
+}
+
+impl SuffixArray {
+
+    /// Indexed text, containing all sequences terminated by `SENTINEL`
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    /// Starting positions of all suffixes of the text in lexicographic order
+    pub fn sa(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// Starting positions of the indexed sequences within the text
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    pub fn len(&self) -> usize {
+        self.sa.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sa.is_empty()
+    }
+
+    /// Inverse suffix array, mapping text positions to their rank among all suffixes
+    pub fn inverse(&self) -> Vec<usize> {
+        let mut rank = vec![0; self.sa.len()];
+        for (i, &pos) in self.sa.iter().enumerate() {
+            rank[pos] = i;
+        }
+        rank
+    }
+
+    /// Longest common prefix array computed with Kasai's algorithm in O(n)
+    ///
+    /// Entry `i` holds the length of the longest common prefix of suffixes at ranks `i - 1` and `i`,
+    /// with the first entry being 0. Common prefixes never include a terminator.
+    pub fn lcp(&self) -> Vec<usize> {
+        let n = self.text.len();
+        let rank = self.inverse();
+        let mut lcp = vec![0; n];
+        let mut h = 0_usize;
+        for i in 0..n {
+            if rank[i] == 0 {
+                h = 0;
+                continue;
+            }
+            // Compare suffix with its predecessor in lexicographic order.
+            // Terminators are distinct, so matching stops there.
+            let j = self.sa[rank[i] - 1];
+            while i + h < n && j + h < n && self.text[i + h] == self.text[j + h] && self.text[i + h] != SENTINEL {
+                h += 1;
+            }
+            lcp[rank[i]] = h;
+            // Suffix at `i + 1` shares at least `h - 1` letters with its predecessor
+            h = h.saturating_sub(1);
+        }
+        lcp
+    }
+
+    /// Burrows-Wheeler transform, the letters preceding every suffix in lexicographic order
+    pub fn bwt(&self) -> Vec<u8> {
+        let n = self.text.len();
+        self.sa
+            .iter()
+            .map(|&pos| if pos == 0 { self.text[n - 1] } else { self.text[pos - 1] })
+            .collect()
+    }
+
+    /// Interval `[start, end)` of ranks of all suffixes that begin with `pattern`
+    pub fn search(&self, pattern: &[u8]) -> (usize, usize) {
+        let prefix = |pos: usize| &self.text[pos..std::cmp::min(pos + pattern.len(), self.text.len())];
+        let start = self.sa.partition_point(|&pos| prefix(pos) < pattern);
+        let end = start + self.sa[start..].partition_point(|&pos| prefix(pos) == pattern);
+        (start, end)
+    }
+
+    /// Map a position of the text to the index of its sequence and the offset within that sequence
+    pub fn resolve(&self, pos: usize) -> (usize, usize) {
+        let seq_idx = self.offsets.partition_point(|&offset| offset <= pos) - 1;
+        (seq_idx, pos - self.offsets[seq_idx])
+    }
+
+    /// Index answering longest common extension queries in O(1), see `LceIndex`
+    ///
+    /// Fails with `InvalidInputSize`, if an LCP value does not fit into the 32-bit entries of the index.
+    pub fn lce_index(&self) -> Result<LceIndex> {
+        let lcp = self.lcp()
+            .iter()
+            .map(|&x| u32::try_from(x).map_err(|_| BioError::InvalidInputSize))
+            .collect::<Result<Vec<u32>>>()?;
+        let n = lcp.len();
+        let mut sparse: Vec<Vec<u32>> = vec![lcp];
+        let mut len = 1;
+        while 2 * len <= n {
+            let prev = sparse.last().unwrap();
+            let level = (0..=n - 2 * len).map(|i| prev[i].min(prev[i + len])).collect();
+            sparse.push(level);
+            len *= 2;
+        }
+        Ok(LceIndex { rank: self.inverse(), sparse, offsets: self.offsets.clone(), len: self.text.len() })
+    }
+}
+
+/// Longest common extension queries on the text of a `SuffixArray`
+///
+/// Complexity: O(n log n) preprocessing and space, O(1) per query
+///
+/// The longest common extension of two positions is the length of the longest common prefix of the suffixes
+/// starting there, obtained as the minimum of the LCP array between their ranks using a sparse table.
+/// Extensions end at the terminator of a sequence. Comparing a text with its reverse complement or its reverse
+/// allows to extend matches in both directions, e.g. for tandem repeats and palindromes.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::builders::suffix_array::SuffixArrayBuilder;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// let sa = SuffixArrayBuilder::new().build(&Sequence::from("GATTACATTAC"));
+/// let lce = sa.lce_index().unwrap();
+/// assert_eq!(lce.lce(2, 7), 4);
+/// assert_eq!(lce.lce(1, 6), 5);
+/// assert_eq!(lce.lce(4, 4), 7);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LceIndex {
+    // Rank of the suffix starting at every position of the text
+    rank: Vec<usize>,
+    // Minima of the LCP array within intervals of length 2^k
+    sparse: Vec<Vec<u32>>,
+    // Starting positions of the indexed sequences and length of the text
+    offsets: Vec<usize>,
+    len: usize,
+}
+
+impl LceIndex {
+
+    /// Length of the longest common prefix of the suffixes starting at text positions `i` and `j`
+    pub fn lce(&self, i: usize, j: usize) -> usize {
+        if i >= self.len || j >= self.len {
+            return 0;
+        }
+        if i == j {
+            // Suffix up to the terminator of its sequence
+            let seq_idx = self.offsets.partition_point(|&offset| offset <= i);
+            return self.offsets.get(seq_idx).copied().unwrap_or(self.len) - i - 1;
+        }
+        let (a, b) = (self.rank[i].min(self.rank[j]) + 1, self.rank[i].max(self.rank[j]));
+        let k = (b - a + 1).ilog2() as usize;
+        self.sparse[k][a].min(self.sparse[k][b + 1 - (1 << k)]) as usize
+    }
+}
+
+// Sort suffixes of `text` with SA-IS, the last letter must be the unique minimum of `text`
+fn sais(text: &[usize], alphabet_size: usize) -> Vec<usize> {
+    let n = text.len();
+    if n == 0 {
+        return vec![];
+    }
+    if n == 1 {
+        return vec![0];
+    }
+
+    // Classify suffixes as S-type (smaller than successor) or L-type (larger than successor)
+    let mut stype = vec![false; n];
+    stype[n - 1] = true;
+    for i in (0..n - 1).rev() {
+        stype[i] = text[i] < text[i + 1] || (text[i] == text[i + 1] && stype[i + 1]);
+    }
+    let is_lms = |i: usize| i > 0 && stype[i] && !stype[i - 1];
+
+    // Bucket sizes for every letter
+    let mut bucket_sizes = vec![0_usize; alphabet_size];
+    for &c in text {
+        bucket_sizes[c] += 1;
+    }
+
+    // Step 1: sort LMS substrings by inducing from unsorted LMS positions
+    let mut sa = vec![EMPTY; n];
+    let mut tails = bucket_tails(&bucket_sizes);
+    for i in (1..n).filter(|&i| is_lms(i)) {
+        tails[text[i]] -= 1;
+        sa[tails[text[i]]] = i;
+    }
+    induce(text, &mut sa, &stype, &bucket_sizes);
+
+    // Step 2: name LMS substrings, equal substrings receive equal names
+    let sorted_lms: Vec<usize> = sa.iter().cloned().filter(|&i| is_lms(i)).collect();
+    let mut names = vec![EMPTY; n];
+    let mut name = 0_usize;
+    let mut prev = EMPTY;
+    for &pos in &sorted_lms {
+        if prev == EMPTY || !lms_substrings_equal(text, &stype, prev, pos) {
+            name += 1;
+        }
+        names[pos] = name - 1;
+        prev = pos;
+    }
+
+    // Reduced problem: string of LMS names in text order
+    let lms_positions: Vec<usize> = (1..n).filter(|&i| is_lms(i)).collect();
+    let reduced: Vec<usize> = lms_positions.iter().map(|&i| names[i]).collect();
+
+    // Recurse if names are not unique, otherwise the order follows from names directly
+    let reduced_sa = if name < reduced.len() {
+        sais(&reduced, name)
+    } else {
+        let mut order = vec![0; reduced.len()];
+        for (i, &r) in reduced.iter().enumerate() {
+            order[r] = i;
+        }
+        order
+    };
+
+    // Step 3: induce final order from sorted LMS suffixes
+    sa.fill(EMPTY);
+    let mut tails = bucket_tails(&bucket_sizes);
+    for &r in reduced_sa.iter().rev() {
+        let pos = lms_positions[r];
+        tails[text[pos]] -= 1;
+        sa[tails[text[pos]]] = pos;
+    }
+    induce(text, &mut sa, &stype, &bucket_sizes);
+    sa
+}
+
+// Induce L-type suffixes from left to right, then S-type suffixes from right to left
+fn induce(text: &[usize], sa: &mut [usize], stype: &[bool], bucket_sizes: &[usize]) {
+    let mut heads = bucket_heads(bucket_sizes);
+    for i in 0..sa.len() {
+        if sa[i] != EMPTY && sa[i] > 0 && !stype[sa[i] - 1] {
+            let j = sa[i] - 1;
+            sa[heads[text[j]]] = j;
+            heads[text[j]] += 1;
+        }
+    }
+    let mut tails = bucket_tails(bucket_sizes);
+    for i in (0..sa.len()).rev() {
+        if sa[i] != EMPTY && sa[i] > 0 && stype[sa[i] - 1] {
+            let j = sa[i] - 1;
+            tails[text[j]] -= 1;
+            sa[tails[text[j]]] = j;
+        }
+    }
+}
+
+// Compare LMS substrings starting at `a` and `b` letter by letter and type by type
+fn lms_substrings_equal(text: &[usize], stype: &[bool], a: usize, b: usize) -> bool {
+    let n = text.len();
+    let is_lms = |i: usize| i > 0 && stype[i] && !stype[i - 1];
+    // The last letter is unique, so its LMS substring equals no other
+    if a == n - 1 || b == n - 1 {
+        return false;
+    }
+    let mut i = 0;
+    loop {
+        let a_end = i > 0 && is_lms(a + i);
+        let b_end = i > 0 && is_lms(b + i);
+        if a_end && b_end {
+            return true;
+        }
+        if a_end != b_end || text[a + i] != text[b + i] || stype[a + i] != stype[b + i] {
+            return false;
+        }
+        i += 1;
+    }
+}
+
+fn bucket_heads(bucket_sizes: &[usize]) -> Vec<usize> {
+    let mut sum = 0;
+    bucket_sizes.iter().map(|&size| { sum += size; sum - size }).collect()
+}
+
+fn bucket_tails(bucket_sizes: &[usize]) -> Vec<usize> {
+    let mut sum = 0;
+    bucket_sizes.iter().map(|&size| { sum += size; sum }).collect()
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use crate::ds::graph::Graph;
 use crate::ds::graph::GraphErr;
@@ -11,7 +11,13 @@ use crate::ds::graph::GraphProperties;
 pub struct TrieNode {
     pub substring: Vec<Vec<u8>>,
     pub children: Vec<i64>,
-    pub ending: bool
+    pub ending: bool,
+    // Indices of the words (in insertion order) that end at this node
+    pub word_ids: Vec<usize>,
+    // Aho-Corasick failure link: node of the longest proper suffix that is also in the trie
+    pub link: i64,
+    // Aho-Corasick output link: next node on the failure chain at which a word ends
+    pub output: i64,
 }
 
 impl TrieNode {
@@ -19,7 +25,10 @@ impl TrieNode {
         TrieNode {
             substring: vec![], 
             children: vec![-1; alphabet_len],
-            ending: false
+            ending: false,
+            word_ids: vec![],
+            link: -1,
+            output: -1,
         }
     }
 }
@@ -39,7 +48,11 @@ impl fmt::Display for TrieNode  {
 pub struct Trie {
     graph: Graph<TrieNode, u8>,
     // map letters to positional representation
-    alphabet: HashMap<u8, usize>
+    alphabet: HashMap<u8, usize>,
+    // Number of words inserted so far
+    words: usize,
+    // Failure and output links are up to date with the inserted words
+    linked: bool,
 }
 
 impl Trie {
@@ -57,7 +70,9 @@ impl Trie {
 
         Trie {
             graph: graph,
-            alphabet: temp
+            alphabet: temp,
+            words: 0,
+            linked: false,
         }
     }
 
@@ -100,6 +115,9 @@ impl Trie {
      
         self.graph.get_node_mut(&cur_node_id).data.ending = true;
         self.graph.get_node_mut(&cur_node_id).data.substring.push(substring.clone());
+        self.graph.get_node_mut(&cur_node_id).data.word_ids.push(self.words);
+        self.words += 1;
+        self.linked = false;
 
         Ok(&self.graph)
     }
@@ -116,4 +134,127 @@ impl Trie {
         
         Ok(&self.graph)
     }
+
+    /// Compute failure and output links, turning the trie into an Aho-Corasick automaton
+    ///
+    /// Links are established in breadth-first order, such that the link of every node 
+    /// points to a node closer to the root that was already resolved.
+    pub fn build_links(&mut self) -> Result<&Graph::<TrieNode, u8>, GraphErr> {
+
+        let root_id = self.graph.get_root().ok_or(GraphErr::NoSuchVertex)?;
+        self.graph.get_node_mut(&root_id).data.link = root_id as i64;
+        self.graph.get_node_mut(&root_id).data.output = -1;
+
+        let mut queue = VecDeque::<u64>::from([root_id]);
+        while let Some(cur_node_id) = queue.pop_front() {
+
+            // Items need to be cloned, because 'graph' is modified later
+            let children = self.graph.get_node(&cur_node_id).data.children.clone();
+
+            for (idx, &child) in children.iter().enumerate() {
+                if child == -1 {
+                    continue;
+                }
+                let child_id = child as u64;
+
+                // Follow failure links of the parent until a node with a matching transition is found
+                let mut link = root_id;
+                if cur_node_id != root_id {
+                    let mut fallback = self.graph.get_node(&cur_node_id).data.link as u64;
+                    loop {
+                        let next = self.graph.get_node(&fallback).data.children[idx];
+                        if next != -1 {
+                            link = next as u64;
+                            break;
+                        }
+                        if fallback == root_id {
+                            break;
+                        }
+                        fallback = self.graph.get_node(&fallback).data.link as u64;
+                    }
+                }
+
+                // Output link points to the closest node on the failure chain that terminates a word
+                let link_node = &self.graph.get_node(&link).data;
+                let output = if link_node.ending { link as i64 } else { link_node.output };
+
+                let child_node = self.graph.get_node_mut(&child_id);
+                child_node.data.link = link as i64;
+                child_node.data.output = output;
+                queue.push_back(child_id);
+            }
+        }
+
+        self.linked = true;
+        Ok(&self.graph)
+    }
+
+    /// Report every occurrence of every inserted word in a single pass over `text`
+    ///
+    /// Returns tuples `(word_index, start_position)`, where `word_index` refers to the 
+    /// insertion order of words, ordered by the end position of the occurrence.
+    /// Letters that are not part of the alphabet reset the automaton to the root.
+    ///
+    /// # Example
+    /// ```
+    /// use biogarden::ds::builders::trie::Trie;
+    ///
+    /// let mut trie = Trie::new(&[b'A', b'C', b'G', b'T']);
+    /// trie.build(&vec![b"ACGT".to_vec(), b"CGT".to_vec(), b"GT".to_vec(), b"TAC".to_vec(), b"T".to_vec()]).unwrap();
+    ///
+    /// // Words that are suffixes of other words are reported through output links
+    /// let occurrences = trie.find_all(&b"ACGTAC".to_vec()).unwrap();
+    /// assert_eq!(occurrences, [(0, 0), (1, 1), (2, 2), (4, 3), (3, 3)]);
+    /// ```
+    pub fn find_all<'a, T>(&mut self, text: &'a T) -> Result<Vec<(usize, usize)>, GraphErr>
+        where &'a T: 'a + IntoIterator<Item=&'a u8>
+    {
+        if !self.linked {
+            self.build_links()?;
+        }
+
+        let root_id = self.graph.get_root().ok_or(GraphErr::NoSuchVertex)?;
+        let mut cur_node_id = root_id;
+        let mut occurrences = Vec::<(usize, usize)>::new();
+
+        for (pos, c) in text.into_iter().enumerate() {
+
+            let idx = match self.alphabet.get(c) {
+                Some(x) => *x,
+                None => {
+                    cur_node_id = root_id;
+                    continue;
+                }
+            };
+
+            // Fall back along failure links until the letter can be consumed
+            loop {
+                let next = self.graph.get_node(&cur_node_id).data.children[idx];
+                if next != -1 {
+                    cur_node_id = next as u64;
+                    break;
+                }
+                if cur_node_id == root_id {
+                    break;
+                }
+                cur_node_id = self.graph.get_node(&cur_node_id).data.link as u64;
+            }
+
+            // Collect words ending at the current node and along its output chain
+            let mut out_node_id = if self.graph.get_node(&cur_node_id).data.ending {
+                cur_node_id as i64
+            } else {
+                self.graph.get_node(&cur_node_id).data.output
+            };
+            while out_node_id != -1 {
+                let node = &self.graph.get_node(&(out_node_id as u64)).data;
+                for (word_id, word) in node.word_ids.iter().zip(node.substring.iter()) {
+                    occurrences.push((*word_id, pos + 1 - word.len()));
+                }
+                out_node_id = node.output;
+            }
+        }
+
+        Ok(occurrences)
+    }
 }
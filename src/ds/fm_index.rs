@@ -0,0 +1,368 @@
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use crate::ds::builders::suffix_array::{SuffixArray, SuffixArrayBuilder, SENTINEL};
+use crate::ds::sequence::Sequence;
+use crate::ds::tile::Tile;
+
+// Number of BWT letters covered by one block of occurrence checkpoints
+const BLOCK: usize = 64;
+// Identifies files written by `FmIndex::write`
+const MAGIC: &[u8; 8] = b"BGFMIDX1";
+
+/// FM-index: compressed full-text index on top of the Burrows-Wheeler transform
+///
+/// Occurrences of a pattern are counted by backward search in O(m) rank queries,
+/// independent of the length of the indexed text. Positions are recovered from a suffix array
+/// that is only sampled at every `sample_rate`-th text position, trading query time for memory.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::fm_index::FmIndex;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// let genome = Sequence::from("GATATATGCATATACTT");
+/// let index = FmIndex::from_sequence(&genome, 4);
+///
+/// assert_eq!(index.count(b"ATAT"), 3);
+/// assert_eq!(index.locate(b"ATAT"), [1, 3, 9]);
+/// assert_eq!(index.count(b"GGG"), 0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FmIndex {
+    // Burrows-Wheeler transform of the indexed text
+    bwt: Vec<u8>,
+    // Sorted letters occurring in the BWT
+    alphabet: Vec<u8>,
+    // Mapping of letters to their position in `alphabet`, `usize::MAX` if absent
+    symbols: Vec<usize>,
+    // Number of letters in the text that are smaller than each letter of `alphabet`
+    counts: Vec<usize>,
+    // Occurrences of each letter before every block of `BLOCK` BWT letters
+    checkpoints: Vec<usize>,
+    // Text positions of sampled suffixes, in order of their rank
+    samples: Vec<usize>,
+    // Marks ranks of sampled suffixes
+    sampled: RankBitVector,
+    // Distance between sampled text positions
+    sample_rate: usize,
+    // Starting position of every indexed sequence within the text
+    offsets: Vec<usize>,
+}
+
+impl FmIndex {
+
+    /// Construct FM-index from a suffix array, sampling every `sample_rate`-th text position
+    ///
+    /// Suffixes starting a sequence are always sampled, such that `locate` never walks across a terminator.
+    pub fn new(sa: &SuffixArray, sample_rate: usize) -> FmIndex {
+        let sample_rate = std::cmp::max(sample_rate, 1);
+        let bwt = sa.bwt();
+
+        let mut sampled = RankBitVector::new(bwt.len());
+        let mut samples = Vec::<usize>::new();
+        for (rank, &pos) in sa.sa().iter().enumerate() {
+            if pos.is_multiple_of(sample_rate) || bwt[rank] == SENTINEL {
+                sampled.set(rank);
+                samples.push(pos);
+            }
+        }
+        sampled.build_ranks();
+
+        FmIndex::from_parts(bwt, samples, sampled, sample_rate, sa.offsets().to_vec())
+    }
+
+    /// Construct FM-index over a single sequence
+    pub fn from_sequence(seq: &Sequence, sample_rate: usize) -> FmIndex {
+        FmIndex::new(&SuffixArrayBuilder::new().build(seq), sample_rate)
+    }
+
+    /// Construct FM-index over all sequences of a tile
+    pub fn from_tile(tile: &Tile, sample_rate: usize) -> FmIndex {
+        FmIndex::new(&SuffixArrayBuilder::new().build_tile(tile), sample_rate)
+    }
+
+    // Derive letter counts and occurrence checkpoints from the BWT
+    fn from_parts(bwt: Vec<u8>, samples: Vec<usize>, sampled: RankBitVector, sample_rate: usize, offsets: Vec<usize>) -> FmIndex {
+        let mut frequencies = vec![0_usize; 256];
+        for &c in &bwt {
+            frequencies[c as usize] += 1;
+        }
+
+        let mut alphabet = Vec::<u8>::new();
+        let mut symbols = vec![usize::MAX; 256];
+        let mut counts = Vec::<usize>::new();
+        let mut total = 0;
+        for c in 0..256 {
+            if frequencies[c] > 0 {
+                symbols[c] = alphabet.len();
+                alphabet.push(c as u8);
+                counts.push(total);
+                total += frequencies[c];
+            }
+        }
+
+        // checkpoints[b * sigma + s] = occurrences of `alphabet[s]` in `bwt[..b * BLOCK]`
+        let sigma = alphabet.len();
+        let mut checkpoints = vec![0_usize; (bwt.len() / BLOCK + 1) * sigma];
+        let mut running = vec![0_usize; sigma];
+        for b in 0..bwt.len() / BLOCK + 1 {
+            checkpoints[b * sigma..(b + 1) * sigma].copy_from_slice(&running);
+            for &c in &bwt[b * BLOCK..std::cmp::min((b + 1) * BLOCK, bwt.len())] {
+                running[symbols[c as usize]] += 1;
+            }
+        }
+
+        FmIndex { bwt, alphabet, symbols, counts, checkpoints, samples, sampled, sample_rate, offsets }
+    }
+
+    /// Length of the indexed text, including terminators
+    pub fn len(&self) -> usize {
+        self.bwt.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bwt.is_empty()
+    }
+
+    /// Burrows-Wheeler transform of the indexed text
+    pub fn bwt(&self) -> &[u8] {
+        &self.bwt
+    }
+
+    /// Number of occurrences of letter `c` in `bwt[..i]`
+    pub fn rank(&self, c: u8, i: usize) -> usize {
+        let s = self.symbols[c as usize];
+        if s == usize::MAX {
+            return 0;
+        }
+        let block = i / BLOCK;
+        let skipped = self.bwt[block * BLOCK..i].iter().filter(|&&x| x == c).count();
+        self.checkpoints[block * self.alphabet.len() + s] + skipped
+    }
+
+    /// Position of the `k`-th occurrence (counting from 0) of letter `c` in the BWT
+    pub fn select(&self, c: u8, k: usize) -> Option<usize> {
+        let s = self.symbols[c as usize];
+        if s == usize::MAX {
+            return None;
+        }
+        // Binary search for the last block that is preceded by at most `k` occurrences
+        let sigma = self.alphabet.len();
+        let (mut lo, mut hi) = (0, self.checkpoints.len() / sigma);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if self.checkpoints[mid * sigma + s] <= k {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let block = lo;
+        let mut seen = self.checkpoints[block * sigma + s];
+        for i in block * BLOCK..self.bwt.len() {
+            if self.bwt[i] == c {
+                if seen == k {
+                    return Some(i);
+                }
+                seen += 1;
+            }
+        }
+        None
+    }
+
+    /// Last-to-first mapping: rank of the suffix that starts one position before the suffix at rank `i`
+    pub fn lf(&self, i: usize) -> usize {
+        let c = self.bwt[i];
+        self.counts[self.symbols[c as usize]] + self.rank(c, i)
+    }
+
+    /// Interval `[start, end)` of ranks of all suffixes that begin with `pattern`, found by backward search
+    pub fn backward_search(&self, pattern: &[u8]) -> (usize, usize) {
+        let (mut start, mut end) = (0, self.bwt.len());
+        for &c in pattern.iter().rev() {
+            let s = self.symbols[c as usize];
+            // Terminators are distinct letters, a pattern spanning them cannot occur
+            if s == usize::MAX || c == SENTINEL {
+                return (0, 0);
+            }
+            start = self.counts[s] + self.rank(c, start);
+            end = self.counts[s] + self.rank(c, end);
+            if start >= end {
+                return (0, 0);
+            }
+        }
+        (start, end)
+    }
+
+    /// Number of occurrences of `pattern` in the indexed text
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        let (start, end) = self.backward_search(pattern);
+        end - start
+    }
+
+    /// Sorted text positions of all occurrences of `pattern`
+    pub fn locate(&self, pattern: &[u8]) -> Vec<usize> {
+        let (start, end) = self.backward_search(pattern);
+        let mut positions: Vec<usize> = (start..end).map(|rank| self.suffix_position(rank)).collect();
+        positions.sort_unstable();
+        positions
+    }
+
+    /// Text position of the suffix at `rank`, walking LF-mapping up to the next sampled suffix
+    pub fn suffix_position(&self, rank: usize) -> usize {
+        let mut rank = rank;
+        let mut steps = 0;
+        while !self.sampled.get(rank) {
+            rank = self.lf(rank);
+            steps += 1;
+        }
+        self.samples[self.sampled.rank(rank)] + steps
+    }
+
+    /// Map a position of the text to the index of its sequence and the offset within that sequence
+    pub fn resolve(&self, pos: usize) -> (usize, usize) {
+        let seq_idx = self.offsets.partition_point(|&offset| offset <= pos) - 1;
+        (seq_idx, pos - self.offsets[seq_idx])
+    }
+
+    /// Serialise the index into a writer
+    ///
+    /// Only the BWT, sampled suffix array and sequence offsets are stored,
+    /// rank structures are rebuilt by `FmIndex::read`.
+    pub fn write<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(writer);
+        w.write_all(MAGIC)?;
+        write_usize(&mut w, self.sample_rate)?;
+        write_usize(&mut w, self.bwt.len())?;
+        w.write_all(&self.bwt)?;
+        write_usizes(&mut w, &self.samples)?;
+        write_u64s(&mut w, &self.sampled.bits)?;
+        write_usizes(&mut w, &self.offsets)?;
+        w.flush()
+    }
+
+    /// Deserialise an index written by `FmIndex::write`
+    ///
+    /// Fails with `InvalidData` for files that are not consistent, and with `UnexpectedEof` for truncated files.
+    pub fn read<R: io::Read>(reader: R) -> io::Result<FmIndex> {
+        let mut r = io::BufReader::new(reader);
+        let mut magic = [0_u8; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("Not an FM-index file."));
+        }
+        let sample_rate = read_usize(&mut r)?;
+
+        // Read the BWT incrementally, such that a corrupt length does not cause a huge allocation
+        let len = read_u64(&mut r)?;
+        let mut bwt = Vec::<u8>::new();
+        r.by_ref().take(len).read_to_end(&mut bwt)?;
+        if (bwt.len() as u64) < len {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated FM-index file."));
+        }
+
+        // Lengths of all other parts are bounded by the length of the BWT
+        let n = bwt.len();
+        let samples = read_usizes(&mut r, n)?;
+        let bits = read_u64s(&mut r, n / 64 + 1)?;
+        let offsets = read_usizes(&mut r, n)?;
+        // Sequences start at strictly increasing offsets, the first one at the beginning of the text
+        let sorted = offsets.first().is_none_or(|&offset| offset == 0) && offsets.windows(2).all(|w| w[0] < w[1]);
+        if sample_rate == 0 || bits.len() != n / 64 + 1 || samples.iter().chain(offsets.iter()).any(|&pos| pos >= n)
+            || !sorted || bits.iter().map(|w| w.count_ones() as usize).sum::<usize>() != samples.len() {
+            return Err(invalid_data("Inconsistent FM-index file."));
+        }
+
+        let mut sampled = RankBitVector { bits, ranks: vec![] };
+        sampled.build_ranks();
+        Ok(FmIndex::from_parts(bwt, samples, sampled, sample_rate, offsets))
+    }
+
+    /// Write the index to the given file path
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write(fs::File::create(path)?)
+    }
+
+    /// Read the index from the given file path
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<FmIndex> {
+        FmIndex::read(fs::File::open(path)?)
+    }
+}
+
+// Bit vector supporting constant time rank queries
+#[derive(Clone, Debug)]
+struct RankBitVector {
+    bits: Vec<u64>,
+    // Number of set bits before every word of `bits`
+    ranks: Vec<usize>,
+}
+
+impl RankBitVector {
+
+    fn new(len: usize) -> RankBitVector {
+        RankBitVector { bits: vec![0; len / 64 + 1], ranks: vec![] }
+    }
+
+    fn set(&mut self, i: usize) {
+        self.bits[i / 64] |= 1 << (i % 64);
+    }
+
+    fn get(&self, i: usize) -> bool {
+        self.bits[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    fn build_ranks(&mut self) {
+        let mut total = 0;
+        self.ranks = self.bits.iter().map(|w| { total += w.count_ones() as usize; total - w.count_ones() as usize }).collect();
+    }
+
+    // Number of set bits in `[0, i)`
+    fn rank(&self, i: usize) -> usize {
+        let mask = (1_u64 << (i % 64)) - 1;
+        self.ranks[i / 64] + (self.bits[i / 64] & mask).count_ones() as usize
+    }
+}
+
+fn write_usize<W: io::Write>(w: &mut W, x: usize) -> io::Result<()> {
+    w.write_all(&(x as u64).to_le_bytes())
+}
+
+fn write_usizes<W: io::Write>(w: &mut W, xs: &[usize]) -> io::Result<()> {
+    write_usize(w, xs.len())?;
+    xs.iter().try_for_each(|&x| write_usize(w, x))
+}
+
+fn write_u64s<W: io::Write>(w: &mut W, xs: &[u64]) -> io::Result<()> {
+    write_usize(w, xs.len())?;
+    xs.iter().try_for_each(|&x| w.write_all(&x.to_le_bytes()))
+}
+
+fn read_u64<R: io::Read>(r: &mut R) -> io::Result<u64> {
+    let mut buf = [0_u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_usize<R: io::Read>(r: &mut R) -> io::Result<usize> {
+    read_u64(r).map(|x| x as usize)
+}
+
+// Read a length-prefixed list of at most `max_len` values
+fn read_usizes<R: io::Read>(r: &mut R, max_len: usize) -> io::Result<Vec<usize>> {
+    read_u64s(r, max_len).map(|xs| xs.into_iter().map(|x| x as usize).collect())
+}
+
+fn read_u64s<R: io::Read>(r: &mut R, max_len: usize) -> io::Result<Vec<u64>> {
+    let len = read_usize(r)?;
+    if len > max_len {
+        return Err(invalid_data("Invalid length in FM-index file."));
+    }
+    (0..len).map(|_| read_u64(r)).collect()
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
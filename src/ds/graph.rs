@@ -1,106 +1,254 @@
 use std::collections::{HashMap, HashSet};
 // use rand::{thread_rng, Rng};
-use std::io::Write;
-use std::fs::File;
+use std::io;
 use std::error::Error;
 
 use std::fmt;
 
+use crate::io::graph::{GraphExporter, GraphFormat};
+
 #[derive(Clone, Debug)]
 pub struct GraphProperties { pub directed: bool}
 
+/// Typed index of a node within a `Graph`
+///
+/// Converts from and into the plain `u64` ids accepted by the methods of `Graph`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub u64);
+
+/// Typed index of an edge within a `Graph`
+///
+/// Converts from and into the plain `u64` ids accepted by the methods of `Graph`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeId(pub u64);
+
+impl NodeId {
+    /// Position of the node within the node storage of the graph
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl EdgeId {
+    /// Position of the edge within the edge storage of the graph
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl From<u64> for NodeId {
+    fn from(id: u64) -> Self {
+        NodeId(id)
+    }
+}
+
+impl From<&u64> for NodeId {
+    fn from(id: &u64) -> Self {
+        NodeId(*id)
+    }
+}
+
+impl From<NodeId> for u64 {
+    fn from(id: NodeId) -> Self {
+        id.0
+    }
+}
+
+impl From<u64> for EdgeId {
+    fn from(id: u64) -> Self {
+        EdgeId(id)
+    }
+}
+
+impl From<&u64> for EdgeId {
+    fn from(id: &u64) -> Self {
+        EdgeId(*id)
+    }
+}
+
+impl From<EdgeId> for u64 {
+    fn from(id: EdgeId) -> Self {
+        id.0
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for EdgeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Edge internal struct
 pub struct Edge<T: fmt::Display> {
+    id: u64,
     pub start: u64,
     pub end: u64,
     pub data: Option<T>,
+    // Positions of the edge within the adjacency lists of its start and end node
+    out_pos: usize,
+    in_pos: usize,
+}
+
+impl<T: fmt::Display> Edge<T> {
+    /// Id of the edge
+    pub fn id(&self) -> EdgeId {
+        EdgeId(self.id)
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Node<T: fmt::Display> {
     id: u64,
-    pub incoming: Vec<u64>,
-    pub outgoing: Vec<u64>,
+    // Adjacent edges, modified only by the graph to keep the positions recorded in its edges valid
+    incoming: Vec<u64>,
+    outgoing: Vec<u64>,
     pub data: T,
 }
 
+impl<T: fmt::Display> Node<T> {
+    /// Id of the node
+    pub fn id(&self) -> NodeId {
+        NodeId(self.id)
+    }
+
+    /// Ids of the edges ending in the node
+    pub fn incoming(&self) -> &[u64] {
+        &self.incoming
+    }
+
+    /// Ids of the edges starting from the node
+    pub fn outgoing(&self) -> &[u64] {
+        &self.outgoing
+    }
+}
+
+/// Mapping of ids before and after `Graph::compact`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdMap {
+    nodes: Vec<Option<u64>>,
+    edges: Vec<Option<u64>>,
+}
+
+impl IdMap {
+    /// New id of a node, `None` if no such node existed
+    pub fn node(&self, id: impl Into<NodeId>) -> Option<NodeId> {
+        self.nodes.get(id.into().index()).copied().flatten().map(NodeId)
+    }
+
+    /// New id of an edge, `None` if no such edge existed
+    pub fn edge(&self, id: impl Into<EdgeId>) -> Option<EdgeId> {
+        self.edges.get(id.into().index()).copied().flatten().map(EdgeId)
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Graph data-structure
+///
+/// Nodes and edges are stored in vectors and identified by their position, such that ids are dense,
+/// adjacency lists are accessed in O(1) and algorithms may keep per-node state in vectors of length `node_bound`.
+/// Removed nodes and edges leave a tombstone, ids of the remaining nodes and edges stay valid.
+/// Tombstones are reclaimed by `compact`, which renumbers nodes and edges.
+/// Ids may be handled as plain `u64` or as the typed `NodeId` and `EdgeId`.
+///
+/// # Example
+/// ```
+/// use biogarden::ds::graph::{Graph, GraphProperties, NodeId};
+///
+/// let mut graph = Graph::<char, u32>::new(GraphProperties{directed: true});
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// let c = graph.add_node('c');
+/// let ab = graph.add_edge(&a, &b, Some(1)).unwrap();
+/// graph.add_edge(&b, &c, Some(2)).unwrap();
+///
+/// graph.remove_node(a);
+/// assert_eq!((graph.node_count(), graph.edge_count(), graph.node_bound()), (2, 1, 3));
+/// assert!(!graph.contains_edge(&ab));
+///
+/// // Reclaim the storage of the removed node, `b` and `c` are renumbered
+/// let ids = graph.compact();
+/// assert_eq!(graph.node_bound(), 2);
+/// let c: NodeId = ids.node(c).unwrap();
+/// assert_eq!(graph.node(c).unwrap().data, 'c');
+/// assert_eq!(graph.get_node(&c.into()).data, 'c');
+/// ```
 pub struct Graph<N: fmt::Display, E: fmt::Display> {
-    /// Mapping of vertex ids and vertex values
-    pub nodes: HashMap<u64, Node<N>>,
-    /// Mapping between edges and weights
-    pub edges: HashMap<u64, Edge<E>>,
+    /// Vertices, indexed by their ids, `None` for removed vertices
+    nodes: Vec<Option<Node<N>>>,
+    /// Edges, indexed by their ids, `None` for removed edges
+    edges: Vec<Option<Edge<E>>>,
+    /// Number of vertices and edges, which were not removed
+    node_count: usize,
+    edge_count: usize,
     /// Properties of the represented graph
     properties: GraphProperties,
     /// Id of root node
     root: Option<u64>,
-    // idx
-    idx: u64,
 }
 
 impl<N: fmt::Display + Clone, E: fmt::Display + Clone> Graph<N, E> {
 
     pub fn new(props: GraphProperties) -> Self {
         Graph {
-            nodes: HashMap::new(),
-            edges: HashMap::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            node_count: 0,
+            edge_count: 0,
             properties: props,
             root: None,
-            idx: 0
         }
     }
 
     pub fn add_node(&mut self, data: N) -> u64 {
-        // let mut rng = thread_rng();
-        // TODO: Make sure that indizes are unique
-        // TODO: Improve idx generation by random sequence qenerator
-        // let nid = rng.gen_range(0..10000000000); 
-        let nid = self.idx;
-        self.idx = self.idx.wrapping_add(1);
-        self.nodes.insert(nid, Node{ id: nid, data: data, incoming: vec![], outgoing: vec![],});
+        let nid = self.nodes.len() as u64;
+        self.nodes.push(Some(Node{ id: nid, data, incoming: vec![], outgoing: vec![],}));
+        self.node_count += 1;
         nid
     }   
 
     pub fn add_edge(&mut self, a: &u64, b: &u64, data: Option<E>) -> Result<u64, GraphErr> {
-        
-        // let e = self.has_edge(a, b);
-        // if  e.is_ok() {
-        //     return e;
-        // };
-
-        let id1 = if self.nodes.get(a).is_some() {
-            *a
-        } else {
-            return Err(GraphErr::NoSuchVertex);
-        };
 
-        let id2 = if self.nodes.get(b).is_some() {
-            *b
-        } else {
+        if !self.contains_node(a) || !self.contains_node(b) {
             return Err(GraphErr::NoSuchVertex);
-        };
-        
-        // TODO: Make sure that indizes are unique
-        // TODO: Improve idx generation by random sequence qenerator
-        // let mut rng = thread_rng();
-        let eid = self.idx % 1000000000000;
-        self.idx = self.idx.wrapping_add(1);
+        }
 
-        self.edges.insert(eid, Edge{start: id1, end: id2, data: data});
-        self.nodes.get_mut(&id1).unwrap().outgoing.push(eid);
-        self.nodes.get_mut(&id2).unwrap().incoming.push(eid);
+        let eid = self.edges.len() as u64;
+        let out_pos = self.get_node(a).outgoing.len();
+        let in_pos = self.get_node(b).incoming.len();
+        self.edges.push(Some(Edge{id: eid, start: *a, end: *b, data, out_pos, in_pos}));
+        self.get_node_mut(a).outgoing.push(eid);
+        self.get_node_mut(b).incoming.push(eid);
+        self.edge_count += 1;
 
         Ok(eid)
     }
 
     pub fn get_node(&self, id: &u64) -> & Node<N> {
-        self.nodes.get(id).unwrap()
+        self.node(NodeId(*id)).unwrap()
     }
 
     pub fn get_node_mut(&mut self, id: &u64) -> &mut Node<N> {
-        self.nodes.get_mut(id).unwrap()
+        self.node_mut(NodeId(*id)).unwrap()
+    }
+
+    /// Node with the given id, `None` if it does not exist
+    pub fn node(&self, id: NodeId) -> Option<&Node<N>> {
+        self.nodes.get(id.index()).and_then(|node| node.as_ref())
+    }
+
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut Node<N>> {
+        self.nodes.get_mut(id.index()).and_then(|node| node.as_mut())
+    }
+
+    pub fn contains_node(&self, id: &u64) -> bool {
+        self.node(NodeId(*id)).is_some()
     }
 
     pub fn get_root(&self) -> Option<u64> {
@@ -112,53 +260,76 @@ impl<N: fmt::Display + Clone, E: fmt::Display + Clone> Graph<N, E> {
     }
 
     pub fn get_edge(&self, id: &u64) -> & Edge<E> {
-        self.edges.get(id).unwrap()
+        self.edge(EdgeId(*id)).unwrap()
     }    
 
     pub fn get_edge_mut(&mut self, id: &u64) -> &mut Edge<E> {
-        self.edges.get_mut(id).unwrap()
+        self.edge_mut(EdgeId(*id)).unwrap()
+    }
+
+    /// Edge with the given id, `None` if it does not exist
+    pub fn edge(&self, id: EdgeId) -> Option<&Edge<E>> {
+        self.edges.get(id.index()).and_then(|edge| edge.as_ref())
+    }
+
+    pub fn edge_mut(&mut self, id: EdgeId) -> Option<&mut Edge<E>> {
+        self.edges.get_mut(id.index()).and_then(|edge| edge.as_mut())
+    }
+
+    pub fn contains_edge(&self, id: &u64) -> bool {
+        self.edge(EdgeId(*id)).is_some()
     }
 
+    /// Id of the edge from `a` to `b`, the smallest one if there are parallel edges
+    ///
+    /// Complexity: O(min(d_out(a), d_in(b)))
     pub fn has_edge(&self, a: &u64, b: &u64) -> Result<u64, GraphErr> {
-        let node = self.nodes.get(a);
+        let (Some(start), Some(end)) = (self.node(NodeId(*a)), self.node(NodeId(*b))) else {
+            return Err(GraphErr::NoSuchEdge);
+        };
 
-        let edge_id = match self.nodes.get(a) {
-            Some(x) => x.outgoing.iter().find(|idx| self.edges.get(idx).unwrap().end == *b),
-            _ => None
+        // Scan the shorter of both adjacency lists
+        let edge_id = if start.outgoing.len() <= end.incoming.len() {
+            start.outgoing.iter().filter(|idx| self.get_edge(idx).end == *b).min()
+        } else {
+            end.incoming.iter().filter(|idx| self.get_edge(idx).start == *a).min()
         };
-        
+
         match edge_id {
             Some(id) => Ok(*id),
             _ => Err(GraphErr::NoSuchEdge)
         }
-    }    
+    }
 
+    /// Remove an edge in O(1), the order of the remaining adjacent edges of its nodes may change
     pub fn remove_edge(&mut self, id: &u64) -> Option<Edge<E>> {
 
-        // Check if edge exists in graph
-        if !self.edges.contains_key(id) {
-            return None;
-        }
+        // Check if edge exists in graph, leave a tombstone
+        let edge = self.edges.get_mut(*id as usize).and_then(|edge| edge.take())?;
+        self.edge_count -= 1;
 
-        // Remove outgoing adjacency in corresponding node
-        let out_node_id = self.get_edge(id).start;
-        let out_node_neighbours = &mut self.get_node_mut(&out_node_id).outgoing;
-        let index_out = out_node_neighbours.iter().position(|x| x == id).unwrap();
-        out_node_neighbours.remove(index_out);  
+        // Remove outgoing adjacency in corresponding node, moving the last adjacent edge into its place
+        let outgoing = &mut self.get_node_mut(&edge.start).outgoing;
+        let index_out = edge.out_pos;
+        outgoing.swap_remove(index_out);
+        if let Some(&moved) = outgoing.get(index_out) {
+            self.get_edge_mut(&moved).out_pos = index_out;
+        }
 
         // Remove incoming adjacency in corresponding node
-        let in_node_id = self.get_edge(id).end;
-        let in_node_neighbours = &mut self.get_node_mut(&in_node_id).incoming;
-        let index_in = in_node_neighbours.iter().position(|x| x == id).unwrap();
-        in_node_neighbours.remove(index_in);        
+        let incoming = &mut self.get_node_mut(&edge.end).incoming;
+        let index_in = edge.in_pos;
+        incoming.swap_remove(index_in);
+        if let Some(&moved) = incoming.get(index_in) {
+            self.get_edge_mut(&moved).in_pos = index_in;
+        }
 
-        // Remove edge from storage container
-        self.edges.remove(id)
+        Some(edge)
     }    
 
     pub fn remove_node(&mut self, id: u64) -> Option<Node<N>> {
 
-        if !self.nodes.contains_key(&id) {
+        if !self.contains_node(&id) {
             return None;
         }
 
@@ -170,71 +341,126 @@ impl<N: fmt::Display + Clone, E: fmt::Display + Clone> Graph<N, E> {
         in_edges.iter().for_each( |in_edge| { self.remove_edge(in_edge); });
         out_edges.iter().for_each( |out_edge| { self.remove_edge(out_edge); });
 
-        // Remove node from ndoes hashset
-        self.nodes.remove(&id)
+        // Leave a tombstone in place of the node
+        if self.root == Some(id) {
+            self.root = None;
+        }
+        self.node_count -= 1;
+        self.nodes[id as usize].take()
+    }
+
+    /// Reclaim the storage of removed nodes and edges
+    ///
+    /// Remaining nodes and edges are renumbered consecutively, keeping their relative order.
+    /// Returns the mapping from the previous to the new ids.
+    pub fn compact(&mut self) -> IdMap {
+
+        let mut node_map = vec![None; self.nodes.len()];
+        let mut edge_map = vec![None; self.edges.len()];
+        self.nodes.retain(|node| node.is_some());
+        self.edges.retain(|edge| edge.is_some());
+
+        // Assign new ids
+        for (new_id, node) in self.nodes.iter_mut().flatten().enumerate() {
+            node_map[node.id as usize] = Some(new_id as u64);
+            node.id = new_id as u64;
+        }
+        for (new_id, edge) in self.edges.iter_mut().flatten().enumerate() {
+            edge_map[edge.id as usize] = Some(new_id as u64);
+            edge.id = new_id as u64;
+            edge.start = node_map[edge.start as usize].unwrap();
+            edge.end = node_map[edge.end as usize].unwrap();
+        }
+
+        // Update adjacency lists, positions of edges within the lists are preserved
+        for node in self.nodes.iter_mut().flatten() {
+            for eid in node.incoming.iter_mut().chain(node.outgoing.iter_mut()) {
+                *eid = edge_map[*eid as usize].unwrap();
+            }
+        }
+        self.root = self.root.and_then(|root| node_map[root as usize]);
+
+        IdMap { nodes: node_map, edges: edge_map }
     }
 
     pub fn edges(&self) -> impl Iterator<Item=&u64> {
-        self.edges.keys()
+        self.edges.iter().flatten().map(|edge| &edge.id)
     }
 
     pub fn nodes(&self) -> impl Iterator<Item=&u64> {
-        self.nodes.keys()
+        self.nodes.iter().flatten().map(|node| &node.id)
+    }
+
+    /// Iterator over the typed ids of all nodes, in increasing order
+    pub fn node_ids(&self) -> impl Iterator<Item=NodeId> + '_ {
+        self.nodes.iter().flatten().map(|node| node.id())
+    }
+
+    /// Iterator over the typed ids of all edges, in increasing order
+    pub fn edge_ids(&self) -> impl Iterator<Item=EdgeId> + '_ {
+        self.edges.iter().flatten().map(|edge| edge.id())
     }
     
     // Obtain Iterator over neigbouring nodes or adjacent edges
-    pub fn out_neighbors<'a>(&'a self, id: u64) -> Box<dyn Iterator<Item=&u64>+'a> {
-        match self.nodes.get(&id) {
-            Some(x) => Box::new(x.outgoing.iter().map(|idx| &self.edges.get(idx).unwrap().end)),
+    pub fn out_neighbors<'a>(&'a self, id: u64) -> Box<dyn Iterator<Item=&'a u64>+'a> {
+        match self.node(NodeId(id)) {
+            Some(x) => Box::new(x.outgoing.iter().map(|idx| &self.get_edge(idx).end)),
             _ => Box::new(::std::iter::empty())
         }
     }
 
-    pub fn out_edges<'a>(&'a self, id: u64) -> Box<dyn Iterator<Item=&u64>+'a> {
-        match self.nodes.get(&id) {
+    pub fn out_edges<'a>(&'a self, id: u64) -> Box<dyn Iterator<Item=&'a u64>+'a> {
+        match self.node(NodeId(id)) {
             Some(x) => Box::new(x.outgoing.iter()),
             _ => Box::new(::std::iter::empty())
         }
     }
 
-    pub fn in_neighbors<'a>(&'a self, id: u64) -> Box<dyn Iterator<Item=&u64>+'a> {
-        match self.nodes.get(&id) {
-            Some(x) => Box::new(x.incoming.iter().map(|idx| &self.edges.get(idx).unwrap().end)),
+    pub fn in_neighbors<'a>(&'a self, id: u64) -> Box<dyn Iterator<Item=&'a u64>+'a> {
+        match self.node(NodeId(id)) {
+            Some(x) => Box::new(x.incoming.iter().map(|idx| &self.get_edge(idx).start)),
             _ => Box::new(::std::iter::empty())
         }
     }
 
-    pub fn in_edges<'a>(&'a self, id: u64) -> Box<dyn Iterator<Item=&u64>+'a> {
-        match self.nodes.get(&id) {
+    pub fn in_edges<'a>(&'a self, id: u64) -> Box<dyn Iterator<Item=&'a u64>+'a> {
+        match self.node(NodeId(id)) {
             Some(x) => Box::new(x.incoming.iter()),
             _ => Box::new(::std::iter::empty())
         }
     }    
 
     pub fn edge_count(&self) -> usize {
-        self.edges.len()
+        self.edge_count
     }
 
     pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Upper bound on `NodeId::index` of all nodes, including removed nodes
+    pub fn node_bound(&self) -> usize {
         self.nodes.len()
     }
+
+    /// Upper bound on `EdgeId::index` of all edges, including removed edges
+    pub fn edge_bound(&self) -> usize {
+        self.edges.len()
+    }
     
     pub fn node_degree(&self, id: &u64) -> Option<usize> {
-        self.nodes.get(id).map(|x| x.outgoing.len() + x.incoming.len())
+        self.node(NodeId(*id)).map(|x| x.outgoing.len() + x.incoming.len())
     }
 
     pub fn reverse(&mut self) {
     
-        for edge in self.edges.values_mut() {
-            let temp = edge.start;
-            edge.start = edge.end;
-            edge.end = temp;
+        for edge in self.edges.iter_mut().flatten() {
+            std::mem::swap(&mut edge.start, &mut edge.end);
+            std::mem::swap(&mut edge.out_pos, &mut edge.in_pos);
         }
 
-        for node in self.nodes.values_mut() {
-            let temp = node.outgoing.clone();
-            node.outgoing = node.incoming.clone();
-            node.incoming = temp;   
+        for node in self.nodes.iter_mut().flatten() {
+            std::mem::swap(&mut node.outgoing, &mut node.incoming);
         }
     }
 
@@ -249,7 +475,7 @@ impl<N: fmt::Display + Clone, E: fmt::Display + Clone> Graph<N, E> {
 
         // Map and copy overlay nodes to subgraph
         for id in overlay {
-            if self.nodes.contains_key(id) {
+            if self.contains_node(id) {
                 let new_id = g.add_node(self.get_node(id).data.clone());
                 id_map.insert(*id, new_id);
             }
@@ -268,27 +494,14 @@ impl<N: fmt::Display + Clone, E: fmt::Display + Clone> Graph<N, E> {
         g
     }
 
-    pub fn write_dot(&self, f: &str) {
-        let mut w = File::create(f).unwrap();
-        if self.properties.directed {
-            writeln!(&mut w, "digraph sample {{").unwrap();
-        }
-        else {
-            writeln!(&mut w, "strict graph sample {{").unwrap();
-        }
-        for edge in self.edges.values() {
-            match (self.properties.directed, edge.data.as_ref()) {
-                (true, Some(data)) =>  writeln!(&mut w, "  {} -> {} [ label = \"{}\" ];", edge.start, edge.end, data).unwrap(),
-                (true, None) => writeln!(&mut w, "  {} -> {};", edge.start, edge.end).unwrap(),
-                (false, Some(data)) => writeln!(&mut w, "  {} -- {} [ label = \"{}\" ];", edge.start, edge.end, data).unwrap(),
-                (false, None) => writeln!(&mut w, "  {} -- {};", edge.start, edge.end).unwrap(),
-            };  
-        }
-        for (id, node) in &self.nodes {
-            writeln!(&mut w, "  {} [ label = \"{}\" ];", id, node.data).unwrap();
-        }
+    /// Properties of the graph, given on construction
+    pub fn properties(&self) -> &GraphProperties {
+        &self.properties
+    }
 
-        writeln!(&mut w, "}}").unwrap();
+    /// Write the graph in DOT format to the given file path, see `io::graph::GraphExporter` for further options
+    pub fn write_dot(&self, f: &str) -> io::Result<()> {
+        GraphExporter::new(self, GraphFormat::Dot).write_file(f)
     }
 }
 
@@ -305,7 +518,19 @@ pub enum GraphErr {
     CannotAddEdge,
 
     /// The given weight is invalid
-    InvalidWeight
+    InvalidWeight,
+
+    /// A cycle of negative total weight is reachable, shortest paths are undefined
+    NegativeCycle,
+
+    /// The graph contains a cycle, but an acyclic graph is required
+    CycleDetected,
+
+    /// The in- and out-degree of the vertex rule out an Eulerian path
+    UnbalancedNode(u64),
+
+    /// The edges of the graph are not connected
+    DisconnectedGraph,
 }
 
 // Allow this type to be treated like an error
@@ -316,6 +541,10 @@ impl Error for GraphErr {
             GraphErr::NoSuchEdge => None,
             GraphErr::CannotAddEdge => None,
             GraphErr::InvalidWeight => None,
+            GraphErr::NegativeCycle => None,
+            GraphErr::CycleDetected => None,
+            GraphErr::UnbalancedNode(_) => None,
+            GraphErr::DisconnectedGraph => None,
         }
     }
 }
@@ -327,6 +556,10 @@ impl fmt::Display for GraphErr {
             GraphErr::NoSuchEdge     => write!(f, "The edge does not exist in the graph!"),
             GraphErr::CannotAddEdge  => write!(f, "The requested ede cannot be added to the graph!"),
             GraphErr::InvalidWeight  => write!(f, "The provided weight is invalid!"),
+            GraphErr::NegativeCycle  => write!(f, "The graph contains a cycle of negative weight!"),
+            GraphErr::CycleDetected  => write!(f, "The graph contains a cycle!"),
+            GraphErr::UnbalancedNode(id) => write!(f, "The degrees of vertex {} rule out an Eulerian path!", id),
+            GraphErr::DisconnectedGraph => write!(f, "The edges of the graph are not connected!"),
         }
     }
 }
@@ -4,6 +4,8 @@ pub mod sequence;
 pub mod tile;
 /// Graph data structure
 pub mod graph;
+/// Compressed full-text index based on the Burrows-Wheeler transform
+pub mod fm_index;
 /// Factories for SuffixTree, Trie, Debruijn Graph...
 pub mod builders;
 /// Cycle detection, Connected components, Eulerian paths... 
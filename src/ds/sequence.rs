@@ -2,6 +2,7 @@
 use ndarray::prelude::*;
 use std::hash::{Hash, Hasher};
 use crate::io::fasta;
+use crate::io::fastq;
 use std::ops::Add;
 use std::fmt; 
 
@@ -80,6 +81,13 @@ impl Sequence {
     }
 }
 
+/// Strand of a double-stranded molecule, on which a feature is located
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strand {
+    Forward,
+    Reverse,
+}
+
 impl Default for Sequence {
     fn default() -> Self {
         Self::new()
@@ -130,6 +138,7 @@ where
 impl<'a,Idx> std::ops::Index<Idx> for &'a Sequence
 where
     Idx: std::slice::SliceIndex<[u8]>,
+    Idx::Output: 'a,
 {
     type Output = Idx::Output;
 
@@ -189,6 +198,12 @@ impl From<fasta::Record> for Sequence {
         Sequence { chain: r.seq().to_vec(), id: Some(r.id().to_string()) }
     }
 }
+// fastq::Record -> Sequence
+impl From<fastq::Record> for Sequence {
+    fn from(r: fastq::Record) -> Self {
+        Sequence { chain: r.seq().to_vec(), id: Some(r.id().to_string()) }
+    }
+}
 // String <- Sequence
 impl From<&Sequence> for String {
     fn from(seq: &Sequence) -> Self {
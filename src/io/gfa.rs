@@ -0,0 +1,443 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::AsRef;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::io::fasta::TextSlice;
+use crate::ds::graph::{Edge, Graph, GraphProperties};
+use crate::ds::sequence::{Sequence, Strand};
+
+/// Graph read from a GFA file, together with its paths
+#[derive(Debug, Clone)]
+pub struct Gfa {
+    /// Segments as nodes, links as edges labelled with the orientations of their segments and the overlap length
+    pub graph: Graph<Sequence, GfaLink>,
+    /// Named paths through the graph
+    pub paths: Vec<GfaPath>,
+    /// Optional fields of segments by their node, such as the length `LN:i:100` of segments without sequence
+    pub segment_tags: HashMap<u64, Vec<String>>,
+}
+
+impl Gfa {
+    /// Length of a segment, given by its `LN` tag if present, otherwise by its sequence
+    pub fn segment_length(&self, id: u64) -> usize {
+        self.segment_tags.get(&id)
+            .and_then(|tags| tags.iter().find_map(|tag| tag.strip_prefix("LN:i:")))
+            .and_then(|len| len.parse().ok())
+            .unwrap_or_else(|| self.graph.get_node(&id).data.len())
+    }
+}
+
+/// Link between oriented segments of a GFA file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GfaLink {
+    /// Orientation of the segment the link starts from
+    pub from_strand: Strand,
+    /// Orientation of the segment the link leads to
+    pub to_strand: Strand,
+    /// Overlap length, `None` if unspecified
+    pub overlap: Option<usize>,
+}
+
+impl fmt::Display for GfaLink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{} {}", orientation(self.from_strand), orientation(self.to_strand), cigar(self.overlap))
+    }
+}
+
+/// Named path through a graph read from a GFA file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GfaPath {
+    pub name: String,
+    /// Nodes visited by the path in the given orientation, in order
+    pub nodes: Vec<(u64, Strand)>,
+    /// Overlaps between consecutive nodes (`None` if unspecified), empty if not given for the whole path
+    pub overlaps: Vec<Option<usize>>,
+}
+
+/// A GFA 1.0 reader.
+///
+/// Segments (`S`), links (`L`) and paths (`P`) are read into a `Gfa`, other record types are skipped.
+/// Each segment becomes a node holding its sequence, with the segment name stored as sequence id,
+/// and its optional fields stored in `Gfa::segment_tags`.
+/// Links become edges between the segments, labelled with the orientation of both segments and the overlap length,
+/// such that the graph can be written back by `Writer::write_gfa`.
+///
+/// # Example
+/// ```
+/// use biogarden::io::gfa::Reader;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// use biogarden::ds::sequence::Strand;
+///
+/// let gfa = b"H\tVN:Z:1.0\nS\ta\tGATTACA\nS\tb\tACTGT\nL\ta\t+\tb\t-\t3M\nP\tp1\ta+,b-\t3M\n";
+/// let result = Reader::new(&gfa[..]).read_graph().unwrap();
+///
+/// assert_eq!(result.graph.node_count(), 2);
+/// let edge = result.graph.edges().next().unwrap();
+/// let link = result.graph.get_edge(edge).data.unwrap();
+/// assert_eq!((link.from_strand, link.to_strand, link.overlap), (Strand::Forward, Strand::Reverse, Some(3)));
+/// assert_eq!(result.paths[0].name, "p1");
+/// let (node, strand) = result.paths[0].nodes[1];
+/// assert_eq!(result.graph.get_node(&node).data, Sequence::from("ACTGT"));
+/// assert_eq!(strand, Strand::Reverse);
+/// ```
+#[derive(Debug)]
+pub struct Reader<B> {
+    reader: B,
+    line: String,
+}
+
+impl Reader<io::BufReader<fs::File>> {
+    /// Read GFA from given file path.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read gfa from {:#?}", path))
+    }
+}
+
+impl<R> Reader<io::BufReader<R>>
+where
+    R: io::Read,
+{
+    pub fn new(reader: R) -> Self {
+        Reader {
+            reader: io::BufReader::new(reader),
+            line: String::new(),
+        }
+    }
+}
+
+impl<B> Reader<B>
+where
+    B: io::BufRead,
+{
+
+    pub fn from_bufread(bufreader: B) -> Self {
+        Reader {
+            reader: bufreader,
+            line: String::new(),
+        }
+    }
+
+    /// Read all records into a graph
+    pub fn read_graph(&mut self) -> io::Result<Gfa> {
+        // Records may appear in any order, segments are added first
+        let mut segments = Vec::<Vec<String>>::new();
+        let mut links = Vec::<Vec<String>>::new();
+        let mut paths = Vec::<Vec<String>>::new();
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                break;
+            }
+            let fields: Vec<String> = self.line.trim_end_matches(['\n', '\r']).split('\t').map(String::from).collect();
+            match fields[0].as_str() {
+                "S" if fields.len() >= 3 => segments.push(fields),
+                "L" if fields.len() >= 6 => links.push(fields),
+                "P" if fields.len() >= 3 => paths.push(fields),
+                "S" | "L" | "P" => return Err(invalid_data("Incomplete GFA record.")),
+                _ => {}
+            }
+        }
+
+        let mut graph = Graph::<Sequence, GfaLink>::new(GraphProperties { directed: true });
+        let mut nodes = HashMap::<String, u64>::new();
+        let mut segment_tags = HashMap::<u64, Vec<String>>::new();
+        for mut segment in segments {
+            let tags = segment.split_off(3);
+            let mut seq = if segment[2] == "*" { Sequence::new() } else { Sequence::from(segment[2].as_str()) };
+            seq.id = Some(segment[1].clone());
+            let id = graph.add_node(seq);
+            if nodes.insert(segment.swap_remove(1), id).is_some() {
+                return Err(invalid_data("Duplicate GFA segment name."));
+            }
+            if !tags.is_empty() {
+                segment_tags.insert(id, tags);
+            }
+        }
+
+        for link in links {
+            let (from, from_strand) = oriented_segment(&nodes, &link[1], &link[2])?;
+            let (to, to_strand) = oriented_segment(&nodes, &link[3], &link[4])?;
+            let overlap = parse_overlap(&link[5])?;
+            graph.add_edge(&from, &to, Some(GfaLink { from_strand, to_strand, overlap }))
+                .map_err(|_| invalid_data("Invalid GFA link."))?;
+        }
+
+        let mut gfa_paths = Vec::<GfaPath>::new();
+        for path in paths {
+            let mut path_nodes = Vec::<(u64, Strand)>::new();
+            for step in path[2].split(',') {
+                if step.len() < 2 {
+                    return Err(invalid_data("Invalid GFA path."));
+                }
+                let (name, orientation) = step.split_at(step.len() - 1);
+                path_nodes.push(oriented_segment(&nodes, name, orientation)?);
+            }
+            let overlaps = match path.get(3).map(String::as_str) {
+                None | Some("*") => vec![],
+                Some(overlaps) => overlaps.split(',').map(parse_overlap).collect::<io::Result<Vec<_>>>()?,
+            };
+            gfa_paths.push(GfaPath { name: path[1].clone(), nodes: path_nodes, overlaps });
+        }
+
+        Ok(Gfa { graph, paths: gfa_paths, segment_tags })
+    }
+}
+
+/// A GFA 1.0 writer.
+///
+/// Graphs with sequences as nodes, e.g. built by `DeBruijnBuilder` or `overlap_graph`,
+/// are written with nodes as segments and edges as links, for inspection in tools such as Bandage.
+/// Graphs read by `Reader` are written back with the orientations of their links and their paths by `write_gfa`.
+/// Segment names have to be unique, writing fails with `InvalidInput` otherwise.
+///
+/// # Example
+/// ```
+/// use biogarden::io::gfa::{Reader, Writer};
+/// use biogarden::ds::builders::debruijn::DeBruijnBuilder;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// let mut reads = Tile::new();
+/// reads.push(Sequence::from("GATTACA"));
+/// let graph = DeBruijnBuilder::new().with_k(4).build(&reads);
+///
+/// // Adjacent 3-mers overlap by 2 bases
+/// let mut writer = Writer::new(Vec::new());
+/// writer.write_graph(&graph, |_| Some(2)).unwrap();
+/// let gfa = writer.into_inner().unwrap();
+///
+/// let result = Reader::new(&gfa[..]).read_graph().unwrap();
+/// assert_eq!(result.graph.node_count(), graph.node_count());
+/// assert_eq!(result.graph.edge_count(), graph.edge_count());
+/// ```
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    writer: io::BufWriter<W>,
+}
+
+impl Writer<fs::File> {
+    /// Write to the given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::File::create(path).map(Writer::new)
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Create a new GFA writer.
+    pub fn new(writer: W) -> Self {
+        Writer {
+            writer: io::BufWriter::new(writer),
+        }
+    }
+
+    /// Write the header line declaring GFA version 1.0.
+    pub fn write_header(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"H\tVN:Z:1.0\n")
+    }
+
+    /// Write a segment with given name and sequence, `*` for an empty sequence.
+    pub fn write_segment(&mut self, name: &str, seq: TextSlice<'_>) -> io::Result<()> {
+        self.write_segment_with_tags(name, seq, &[])
+    }
+
+    /// Write a segment with given name, sequence and optional fields, e.g. `LN:i:100`.
+    pub fn write_segment_with_tags(&mut self, name: &str, seq: TextSlice<'_>, tags: &[String]) -> io::Result<()> {
+        let seq = if seq.is_empty() { b"*" } else { seq };
+        self.writer.write_all(b"S\t")?;
+        self.writer.write_all(name.as_bytes())?;
+        self.writer.write_all(b"\t")?;
+        self.writer.write_all(seq)?;
+        for tag in tags {
+            self.writer.write_all(b"\t")?;
+            self.writer.write_all(tag.as_bytes())?;
+        }
+        self.writer.write_all(b"\n")
+    }
+
+    /// Write a link between oriented segments, with the overlap length given as CIGAR match (`*` if unknown).
+    pub fn write_link(&mut self, from: &str, from_strand: Strand, to: &str, to_strand: Strand,
+                      overlap: Option<usize>) -> io::Result<()> {
+        writeln!(self.writer, "L\t{}\t{}\t{}\t{}\t{}",
+                 from, orientation(from_strand), to, orientation(to_strand), cigar(overlap))
+    }
+
+    /// Write a path visiting the given oriented segments, with the overlaps between them (`*` if empty).
+    pub fn write_path(&mut self, name: &str, segments: &[(&str, Strand)], overlaps: &[Option<usize>]) -> io::Result<()> {
+        let steps: Vec<String> = segments.iter()
+            .map(|(segment, strand)| format!("{}{}", segment, orientation(*strand)))
+            .collect();
+        let overlaps = if overlaps.is_empty() {
+            String::from("*")
+        } else {
+            overlaps.iter().map(|&overlap| cigar(overlap)).collect::<Vec<String>>().join(",")
+        };
+        writeln!(self.writer, "P\t{}\t{}\t{}", name, steps.join(","), overlaps)
+    }
+
+    /// Write header, all nodes as segments and all edges as links of a graph.
+    ///
+    /// Segments are named by the id of their sequence if present, otherwise by the node id.
+    /// Links connect the forward orientations of their segments.
+    /// The overlap of each link is obtained from `overlap`, e.g. `Some(k - 2)` for a De Bruijn graph of k-mers,
+    /// `None` is written as `*`.
+    pub fn write_graph<E, F>(&mut self, graph: &Graph<Sequence, E>, overlap: F) -> io::Result<()>
+    where
+        E: fmt::Display + Clone,
+        F: Fn(&Edge<E>) -> Option<usize>,
+    {
+        self.write_links(graph, None, |edge| (Strand::Forward, Strand::Forward, overlap(edge)))?;
+        Ok(())
+    }
+
+    /// Write header, segments, links and paths of a graph read by `Reader`.
+    ///
+    /// Links are written with the orientations of their segments and their overlap, segments with their
+    /// optional fields and paths with their overlaps, such that reading the result yields the same graph.
+    ///
+    /// # Example
+    /// ```
+    /// use biogarden::io::gfa::{Reader, Writer};
+    ///
+    /// let gfa = "H\tVN:Z:1.0\nS\ta\tGATTACA\nS\tb\t*\tLN:i:5\nL\ta\t+\tb\t-\t3M\nL\tb\t+\ta\t-\t*\nP\tp1\ta+,b-\t3M\n";
+    /// let result = Reader::new(gfa.as_bytes()).read_graph().unwrap();
+    ///
+    /// let mut writer = Writer::new(Vec::new());
+    /// writer.write_gfa(&result).unwrap();
+    /// assert_eq!(String::from_utf8(writer.into_inner().unwrap()).unwrap(), gfa);
+    /// ```
+    pub fn write_gfa(&mut self, gfa: &Gfa) -> io::Result<()> {
+        let names = self.write_links(&gfa.graph, Some(&gfa.segment_tags), |edge| {
+            let link = edge.data.unwrap_or(GfaLink { from_strand: Strand::Forward, to_strand: Strand::Forward, overlap: None });
+            (link.from_strand, link.to_strand, link.overlap)
+        })?;
+        for path in &gfa.paths {
+            let segments: Vec<(&str, Strand)> = path.nodes.iter()
+                .map(|(id, strand)| (names[id].as_str(), *strand))
+                .collect();
+            self.write_path(&path.name, &segments, &path.overlaps)?;
+        }
+        Ok(())
+    }
+
+    // Write header, all nodes as segments and all edges as links, with orientations and overlap given by `link`.
+    // Returns the names of the segments by their node.
+    fn write_links<E, F>(&mut self, graph: &Graph<Sequence, E>, tags: Option<&HashMap<u64, Vec<String>>>,
+                         link: F) -> io::Result<HashMap<u64, String>>
+    where
+        E: fmt::Display + Clone,
+        F: Fn(&Edge<E>) -> (Strand, Strand, Option<usize>),
+    {
+        // Names have to identify segments unambiguously
+        let names: HashMap<u64, String> = graph.nodes().map(|&id| (id, segment_name(graph, id))).collect();
+        let mut unique = HashSet::<&str>::new();
+        if !names.values().all(|name| unique.insert(name)) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Duplicate GFA segment name."));
+        }
+
+        self.write_header()?;
+        let mut nodes: Vec<&u64> = graph.nodes().collect();
+        nodes.sort_unstable();
+        for id in nodes {
+            let segment_tags = tags.and_then(|tags| tags.get(id)).map_or(&[][..], |tags| &tags[..]);
+            self.write_segment_with_tags(&names[id], &graph.get_node(id).data.chain, segment_tags)?;
+        }
+        let mut edges: Vec<&u64> = graph.edges().collect();
+        edges.sort_unstable();
+        for id in edges {
+            let edge = graph.get_edge(id);
+            let (from_strand, to_strand, overlap) = link(edge);
+            self.write_link(&names[&edge.start], from_strand, &names[&edge.end], to_strand, overlap)?;
+        }
+        Ok(names)
+    }
+
+    /// Write a path through the nodes of a graph, named consistently with `write_graph`.
+    pub fn write_graph_path<E>(&mut self, graph: &Graph<Sequence, E>, name: &str, nodes: &[u64]) -> io::Result<()>
+    where
+        E: fmt::Display + Clone,
+    {
+        let names: Vec<String> = nodes.iter().map(|id| segment_name(graph, *id)).collect();
+        let segments: Vec<(&str, Strand)> = names.iter().map(|name| (name.as_str(), Strand::Forward)).collect();
+        self.write_path(name, &segments, &[])
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flush the writer and return the underlying writer.
+    pub fn into_inner(self) -> io::Result<W> {
+        self.writer.into_inner().map_err(|e| e.into_error())
+    }
+}
+
+fn segment_name<E: fmt::Display + Clone>(graph: &Graph<Sequence, E>, id: u64) -> String {
+    graph.get_node(&id).data.id.clone().unwrap_or_else(|| id.to_string())
+}
+
+fn orientation(strand: Strand) -> char {
+    match strand {
+        Strand::Forward => '+',
+        Strand::Reverse => '-',
+    }
+}
+
+fn cigar(overlap: Option<usize>) -> String {
+    match overlap {
+        Some(len) => format!("{}M", len),
+        None => String::from("*"),
+    }
+}
+
+// Overlap length on the source segment, counting M, =, X and D operations
+fn parse_overlap(cigar: &str) -> io::Result<Option<usize>> {
+    if cigar == "*" {
+        return Ok(None);
+    }
+    let mut overlap = 0_usize;
+    let mut len = String::new();
+    for c in cigar.chars() {
+        if c.is_ascii_digit() {
+            len.push(c);
+            continue;
+        }
+        let n: usize = len.parse().map_err(|_| invalid_data("Invalid GFA overlap."))?;
+        match c {
+            'M' | '=' | 'X' | 'D' => overlap += n,
+            'I' | 'S' | 'H' | 'N' | 'P' => {}
+            _ => return Err(invalid_data("Invalid GFA overlap.")),
+        }
+        len.clear();
+    }
+    // Trailing length without an operation
+    if !len.is_empty() {
+        return Err(invalid_data("Invalid GFA overlap."));
+    }
+    Ok(Some(overlap))
+}
+
+// Node of a segment and the given orientation
+fn oriented_segment(nodes: &HashMap<String, u64>, name: &str, orientation: &str) -> io::Result<(u64, Strand)> {
+    let strand = match orientation {
+        "+" => Strand::Forward,
+        "-" => Strand::Reverse,
+        _ => return Err(invalid_data("Invalid GFA orientation.")),
+    };
+    let id = *nodes.get(name).ok_or_else(|| invalid_data("Reference to unknown GFA segment."))?;
+    Ok((id, strand))
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_owned())
+}
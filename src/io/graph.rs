@@ -0,0 +1,288 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use crate::ds::graph::{Edge, Graph, Node};
+
+/// File format of an exported graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT language, rendered e.g. by `dot -Tsvg`
+    Dot,
+    /// GraphML, read e.g. by Cytoscape, Gephi and yEd
+    GraphMl,
+    /// JSON node-link format, as used by d3.js and networkx
+    Json,
+}
+
+type Label<'a, T> = Box<dyn Fn(&T) -> Option<String> + 'a>;
+type Attributes<'a, T> = Box<dyn Fn(&T) -> Vec<(String, String)> + 'a>;
+
+// Label and attributes of a node or edge, obtained from the closures of the exporter
+struct Element {
+    id: u64,
+    ends: Option<(u64, u64)>,
+    label: Option<String>,
+    attributes: Vec<(String, String)>,
+}
+
+/// Export graphs to DOT, GraphML or JSON
+///
+/// Nodes and edges are labelled using their `Display` implementation by default.
+/// Custom labels and further attributes, such as colour or width, are computed by closures,
+/// e.g. to colour the nodes of an assembly graph by coverage.
+/// Attributes of the whole graph, such as `rankdir` in DOT, are given as key-value pairs.
+/// Nodes and edges are identified by their ids and written in ascending order of their ids.
+///
+/// # Example
+/// ```
+/// use biogarden::io::graph::{GraphExporter, GraphFormat};
+/// use biogarden::ds::graph::{Graph, GraphProperties};
+///
+/// let mut graph = Graph::<String, usize>::new(GraphProperties{directed: true});
+/// let a = graph.add_node(String::from("ACG"));
+/// let b = graph.add_node(String::from("CGT"));
+/// graph.add_edge(&a, &b, Some(12)).unwrap();
+///
+/// let mut dot = Vec::new();
+/// GraphExporter::new(&graph, GraphFormat::Dot)
+///     .with_graph_attribute("rankdir", "LR")
+///     .with_edge_label(|_| None)
+///     .with_edge_attributes(|e| vec![(String::from("penwidth"), (e.data.unwrap() / 4).to_string())])
+///     .write(&mut dot)
+///     .unwrap();
+/// assert_eq!(String::from_utf8(dot).unwrap(),
+///            "digraph \"G\" {\n  rankdir=\"LR\";\n  0 [label=\"ACG\"];\n  1 [label=\"CGT\"];\n  0 -> 1 [penwidth=\"3\"];\n}\n");
+///
+/// let mut json = Vec::new();
+/// GraphExporter::new(&graph, GraphFormat::Json).write(&mut json).unwrap();
+/// assert!(String::from_utf8(json).unwrap().contains("\"links\": [\n    {\"id\": 0, \"source\": 0, \"target\": 1, \"label\": \"12\"}"));
+/// ```
+pub struct GraphExporter<'a, N: fmt::Display, E: fmt::Display> {
+    graph: &'a Graph<N, E>,
+    format: GraphFormat,
+    name: String,
+    graph_attributes: Vec<(String, String)>,
+    node_label: Label<'a, Node<N>>,
+    edge_label: Label<'a, Edge<E>>,
+    node_attributes: Attributes<'a, Node<N>>,
+    edge_attributes: Attributes<'a, Edge<E>>,
+}
+
+impl<'a, N: fmt::Display + Clone, E: fmt::Display + Clone> GraphExporter<'a, N, E> {
+
+    /// Export `graph` in the given format
+    pub fn new(graph: &'a Graph<N, E>, format: GraphFormat) -> Self {
+        GraphExporter {
+            graph,
+            format,
+            name: String::from("G"),
+            graph_attributes: vec![],
+            node_label: Box::new(|node| Some(node.data.to_string())),
+            edge_label: Box::new(|edge| edge.data.as_ref().map(|data| data.to_string())),
+            node_attributes: Box::new(|_| vec![]),
+            edge_attributes: Box::new(|_| vec![]),
+        }
+    }
+
+    /// Name of the graph, `G` by default
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_owned();
+        self
+    }
+
+    /// Add an attribute of the whole graph, e.g. `rankdir` or `label` in DOT
+    pub fn with_graph_attribute(mut self, key: &str, value: &str) -> Self {
+        self.graph_attributes.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Label of every node, `None` for no label
+    pub fn with_node_label<F: Fn(&Node<N>) -> Option<String> + 'a>(mut self, label: F) -> Self {
+        self.node_label = Box::new(label);
+        self
+    }
+
+    /// Label of every edge, `None` for no label
+    pub fn with_edge_label<F: Fn(&Edge<E>) -> Option<String> + 'a>(mut self, label: F) -> Self {
+        self.edge_label = Box::new(label);
+        self
+    }
+
+    /// Further attributes of every node as key-value pairs, e.g. `fillcolor` in DOT
+    pub fn with_node_attributes<F: Fn(&Node<N>) -> Vec<(String, String)> + 'a>(mut self, attributes: F) -> Self {
+        self.node_attributes = Box::new(attributes);
+        self
+    }
+
+    /// Further attributes of every edge as key-value pairs, e.g. `penwidth` in DOT
+    pub fn with_edge_attributes<F: Fn(&Edge<E>) -> Vec<(String, String)> + 'a>(mut self, attributes: F) -> Self {
+        self.edge_attributes = Box::new(attributes);
+        self
+    }
+
+    /// Write the graph to the given file path
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write(fs::File::create(path)?)
+    }
+
+    /// Write the graph to any writer
+    pub fn write<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let graph = self.graph;
+        let nodes: Vec<Element> = graph.nodes().map(|id| {
+            let node = graph.get_node(id);
+            Element { id: *id, ends: None, label: (self.node_label)(node), attributes: (self.node_attributes)(node) }
+        }).collect();
+        let edges: Vec<Element> = graph.edges().map(|id| {
+            let edge = graph.get_edge(id);
+            Element {
+                id: *id,
+                ends: Some((edge.start, edge.end)),
+                label: (self.edge_label)(edge),
+                attributes: (self.edge_attributes)(edge),
+            }
+        }).collect();
+
+        let mut w = io::BufWriter::new(writer);
+        let directed = graph.properties().directed;
+        match self.format {
+            GraphFormat::Dot => self.write_dot(&mut w, directed, &nodes, &edges)?,
+            GraphFormat::GraphMl => self.write_graphml(&mut w, directed, &nodes, &edges)?,
+            GraphFormat::Json => self.write_json(&mut w, directed, &nodes, &edges)?,
+        }
+        w.flush()
+    }
+
+    fn write_dot<W: io::Write>(&self, w: &mut W, directed: bool, nodes: &[Element], edges: &[Element]) -> io::Result<()> {
+        let (kind, arrow) = if directed { ("digraph", "->") } else { ("strict graph", "--") };
+        writeln!(w, "{} \"{}\" {{", kind, escape_dot(&self.name))?;
+        for (key, value) in &self.graph_attributes {
+            writeln!(w, "  {}=\"{}\";", key, escape_dot(value))?;
+        }
+        for node in nodes {
+            writeln!(w, "  {}{};", node.id, dot_attributes(node))?;
+        }
+        for edge in edges {
+            let (start, end) = edge.ends.unwrap();
+            writeln!(w, "  {} {} {}{};", start, arrow, end, dot_attributes(edge))?;
+        }
+        writeln!(w, "}}")
+    }
+
+    fn write_graphml<W: io::Write>(&self, w: &mut W, directed: bool, nodes: &[Element], edges: &[Element]) -> io::Result<()> {
+        writeln!(w, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(w, "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">")?;
+
+        // Attributes have to be declared as keys ahead of the graph, with separate keys for graph, nodes and edges
+        let graph_keys: Vec<&str> = self.graph_attributes.iter().map(|(key, _)| key.as_str()).collect();
+        for (domain, keys) in [("graph", graph_keys), ("node", attribute_keys(nodes)), ("edge", attribute_keys(edges))] {
+            for key in keys {
+                writeln!(w, "  <key id=\"{}_{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"string\"/>",
+                         domain, escape_xml(key), domain, escape_xml(key))?;
+            }
+        }
+
+        let edgedefault = if directed { "directed" } else { "undirected" };
+        writeln!(w, "  <graph id=\"{}\" edgedefault=\"{}\">", escape_xml(&self.name), edgedefault)?;
+        for (key, value) in &self.graph_attributes {
+            writeln!(w, "    <data key=\"graph_{}\">{}</data>", escape_xml(key), escape_xml(value))?;
+        }
+        for node in nodes {
+            writeln!(w, "    <node id=\"n{}\">", node.id)?;
+            for (key, value) in element_attributes(node) {
+                writeln!(w, "      <data key=\"node_{}\">{}</data>", escape_xml(key), escape_xml(value))?;
+            }
+            writeln!(w, "    </node>")?;
+        }
+        for edge in edges {
+            let (start, end) = edge.ends.unwrap();
+            writeln!(w, "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">", edge.id, start, end)?;
+            for (key, value) in element_attributes(edge) {
+                writeln!(w, "      <data key=\"edge_{}\">{}</data>", escape_xml(key), escape_xml(value))?;
+            }
+            writeln!(w, "    </edge>")?;
+        }
+        writeln!(w, "  </graph>")?;
+        writeln!(w, "</graphml>")
+    }
+
+    fn write_json<W: io::Write>(&self, w: &mut W, directed: bool, nodes: &[Element], edges: &[Element]) -> io::Result<()> {
+        writeln!(w, "{{")?;
+        writeln!(w, "  \"directed\": {},", directed)?;
+        writeln!(w, "  \"multigraph\": true,")?;
+        let graph: Vec<String> = std::iter::once((String::from("name"), self.name.clone()))
+            .chain(self.graph_attributes.iter().cloned())
+            .map(|(key, value)| format!("\"{}\": \"{}\"", escape_json(&key), escape_json(&value)))
+            .collect();
+        writeln!(w, "  \"graph\": {{{}}},", graph.join(", "))?;
+
+        for (field, elements, last) in [("nodes", nodes, false), ("links", edges, true)] {
+            writeln!(w, "  \"{}\": [", field)?;
+            for (i, element) in elements.iter().enumerate() {
+                let mut fields = vec![format!("\"id\": {}", element.id)];
+                if let Some((start, end)) = element.ends {
+                    fields.push(format!("\"source\": {}", start));
+                    fields.push(format!("\"target\": {}", end));
+                }
+                fields.extend(element_attributes(element)
+                    .map(|(key, value)| format!("\"{}\": \"{}\"", escape_json(key), escape_json(value))));
+                let separator = if i + 1 < elements.len() { "," } else { "" };
+                writeln!(w, "    {{{}}}{}", fields.join(", "), separator)?;
+            }
+            writeln!(w, "  ]{}", if last { "" } else { "," })?;
+        }
+        writeln!(w, "}}")
+    }
+}
+
+// Label followed by the further attributes of a node or edge
+fn element_attributes(element: &Element) -> impl Iterator<Item = (&str, &str)> {
+    element.label.iter().map(|label| ("label", label.as_str()))
+        .chain(element.attributes.iter().map(|(key, value)| (key.as_str(), value.as_str())))
+}
+
+// Names of all attributes occurring for the given elements, in order of their first occurrence
+fn attribute_keys(elements: &[Element]) -> Vec<&str> {
+    let mut seen = HashSet::<&str>::new();
+    elements.iter()
+        .flat_map(|element| element_attributes(element).map(|(key, _)| key))
+        .filter(|key| seen.insert(key))
+        .collect()
+}
+
+fn dot_attributes(element: &Element) -> String {
+    let attributes: Vec<String> = element_attributes(element)
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_dot(value)))
+        .collect();
+    match attributes.is_empty() {
+        true => String::new(),
+        false => format!(" [{}]", attributes.join(", ")),
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
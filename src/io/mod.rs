@@ -1 +1,4 @@
-pub mod fasta;
\ No newline at end of file
+pub mod fasta;
+pub mod fastq;
+pub mod gfa;
+pub mod graph;
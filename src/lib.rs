@@ -6,7 +6,7 @@ pub mod analysis;
 pub mod processing;
 /// Data structures
 pub mod ds;
-/// Fasta Input/Output
+/// Fasta, Fastq, GFA Input/Output
 pub mod io;
 /// Error types
 pub mod error;
\ No newline at end of file
@@ -1,72 +1,88 @@
-use lazy_static::lazy_static;
-use std::collections::hash_map::HashMap;
-
-lazy_static! {
-    // mRNA <-> amino-acid translation table (codon table)
-    pub static ref CODON_TABLE : HashMap<&'static str, &'static str> = HashMap::from([
-        ("UUU", "F"),
-        ("CUU", "L"),
-        ("AUU", "I"),
-        ("GUU", "V"),
-        ("UUC", "F"),
-        ("CUC", "L"),
-        ("AUC", "I"),
-        ("GUC", "V"),
-        ("UUA", "L"),
-        ("CUA", "L"),
-        ("AUA", "I"),
-        ("GUA", "V"),
-        ("UUG", "L"),
-        ("CUG", "L"),
-        ("AUG", "M"),
-        ("GUG", "V"),
-        ("UCU", "S"),
-        ("CCU", "P"),
-        ("ACU", "T"),
-        ("GCU", "A"),
-        ("UCC", "S"),
-        ("CCC", "P"),
-        ("ACC", "T"),
-        ("GCC", "A"),
-        ("UCA", "S"),
-        ("CCA", "P"),
-        ("ACA", "T"),
-        ("GCA", "A"),
-        ("UCG", "S"),
-        ("CCG", "P"),
-        ("ACG", "T"),
-        ("GCG", "A"),
-        ("UAU", "Y"),
-        ("CAU", "H"),
-        ("AAU", "N"),
-        ("GAU", "D"),
-        ("UAC", "Y"),
-        ("CAC", "H"),
-        ("AAC", "N"),
-        ("GAC", "D"),
-        ("UAA", "Stop"),
-        ("CAA", "Q"),
-        ("AAA", "K"),
-        ("GAA", "E"),
-        ("UAG", "Stop"),
-        ("CAG", "Q"),
-        ("AAG", "K"),
-        ("GAG", "E"),
-        ("UGU", "C"),
-        ("CGU", "R"),
-        ("AGU", "S"),
-        ("GGU", "G"),
-        ("UGC", "C"),
-        ("CGC", "R"),
-        ("AGC", "S"),
-        ("GGC", "G"),
-        ("UGA", "Stop"),
-        ("CGA", "R"),
-        ("AGA", "R"),
-        ("GGA", "G"),
-        ("UGG", "W"),
-        ("CGG", "R"),
-        ("AGG", "R"),
-        ("GGG", "G"),
-    ]);
-}
+// NCBI genetic codes, source: https://www.ncbi.nlm.nih.gov/Taxonomy/Utils/wprintgc.cgi
+//
+// Each table lists the amino acid and the start codon marker of all 64 codons,
+// ordered by first, second and third nucleotide in the order T, C, A, G (TTT, TTC, TTA, TTG, TCT, ...).
+// Stop codons are encoded as '*', start codons as 'M', in addition to being translated to the amino acid.
+pub static GENETIC_CODES: [(usize, &str, &[u8; 64], &[u8; 64]); 27] = [
+    (1, "Standard",
+        b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"---M------**--*----M---------------M----------------------------"),
+    (2, "Vertebrate Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG",
+        b"----------**--------------------MMMM----------**---M------------"),
+    (3, "Yeast Mitochondrial",
+        b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------**----------------------MM---------------M------------"),
+    (4, "Mold, Protozoan, and Coelenterate Mitochondrial; Mycoplasma; Spiroplasma",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"--MM------**-------M------------MMMM---------------M------------"),
+    (5, "Invertebrate Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG",
+        b"---M------**--------------------MMMM---------------M------------"),
+    (6, "Ciliate, Dasycladacean and Hexamita Nuclear",
+        b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"--------------*--------------------M----------------------------"),
+    (9, "Echinoderm and Flatworm Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        b"----------**-----------------------M---------------M------------"),
+    (10, "Euplotid Nuclear",
+        b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------**-----------------------M----------------------------"),
+    (11, "Bacterial, Archaeal and Plant Plastid",
+        b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"---M------**--*----M------------MMMM---------------M------------"),
+    (12, "Alternative Yeast Nuclear",
+        b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------**--*----M---------------M----------------------------"),
+    (13, "Ascidian Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG",
+        b"---M------**----------------------MM---------------M------------"),
+    (14, "Alternative Flatworm Mitochondrial",
+        b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        b"-----------*-----------------------M----------------------------"),
+    (15, "Blepharisma Macronuclear",
+        b"FFLLSSSSYY*QCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------*---*--------------------M----------------------------"),
+    (16, "Chlorophycean Mitochondrial",
+        b"FFLLSSSSYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------*---*--------------------M----------------------------"),
+    (21, "Trematode Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        b"----------**-----------------------M---------------M------------"),
+    (22, "Scenedesmus obliquus Mitochondrial",
+        b"FFLLSS*SYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"------*---*---*--------------------M----------------------------"),
+    (23, "Thraustochytrium Mitochondrial",
+        b"FF*LSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"--*-------**--*-----------------M--M---------------M------------"),
+    (24, "Rhabdopleuridae Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        b"---M------**-------M---------------M---------------M------------"),
+    (25, "Candidate Division SR1 and Gracilibacteria",
+        b"FFLLSSSSYY**CCGWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"---M------**-----------------------M---------------M------------"),
+    (26, "Pachysolen tannophilus Nuclear",
+        b"FFLLSSSSYY**CC*WLLLAPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------**--*----M---------------M----------------------------"),
+    (27, "Karyorelict Nuclear",
+        b"FFLLSSSSYYQQCCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"--------------*--------------------M----------------------------"),
+    (28, "Condylostoma Nuclear",
+        b"FFLLSSSSYYQQCCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------**--*--------------------M----------------------------"),
+    (29, "Mesodinium Nuclear",
+        b"FFLLSSSSYYYYCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"--------------*--------------------M----------------------------"),
+    (30, "Peritrich Nuclear",
+        b"FFLLSSSSYYEECC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"--------------*--------------------M----------------------------"),
+    (31, "Blastocrithidia Nuclear",
+        b"FFLLSSSSYYEECCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------**-----------------------M----------------------------"),
+    (32, "Balanophoraceae Plastid",
+        b"FFLLSSSSYY*WCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"---M------*---*----M------------MMMM---------------M------------"),
+    (33, "Cephalodiscidae Mitochondrial",
+        b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        b"---M-------*-------M---------------M---------------M------------"),
+];
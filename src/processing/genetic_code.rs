@@ -0,0 +1,149 @@
+use crate::ds::sequence::Sequence;
+use crate::error::{BioError, Result};
+use crate::processing::constants::GENETIC_CODES;
+
+/// Translation table mapping codons onto amino acids, as defined by the NCBI genetic codes
+///
+/// Besides the standard code (table 1), mitochondria, plastids and several bacterial and nuclear lineages
+/// deviate in the meaning of some codons, see <https://www.ncbi.nlm.nih.gov/Taxonomy/Utils/wprintgc.cgi>.
+/// Each table defines its stop codons and its start codons, which include alternative start codons
+/// such as `GUG` or `UUG` that initiate translation with methionine.
+/// Codons are accepted in DNA or RNA alphabet, in upper or lower case.
+/// Tables 27, 28 and 31 reassign stop codons depending on the context, these codons are translated to their amino acid.
+///
+/// # Example
+/// ```
+/// use biogarden::processing::genetic_code::GeneticCode;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// let standard = GeneticCode::standard();
+/// let mito = GeneticCode::new(2).unwrap();
+/// assert_eq!(mito.name(), "Vertebrate Mitochondrial");
+///
+/// // UGA encodes tryptophan in vertebrate mitochondria, AGA terminates translation
+/// assert_eq!(standard.translate_codon(b"UGA"), Some(b'*'));
+/// assert_eq!(mito.translate_codon(b"UGA"), Some(b'W'));
+/// assert!(mito.is_stop(b"AGA"));
+///
+/// // Alternative start codons
+/// assert!(mito.is_start(b"ATA"));
+/// assert_eq!(standard.start_codons(), vec![Sequence::from("TTG"), Sequence::from("CTG"), Sequence::from("ATG")]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GeneticCode {
+    id: usize,
+    name: &'static str,
+    // Amino acids and start markers of the codons in NCBI order, see `constants::GENETIC_CODES`
+    amino_acids: &'static [u8; 64],
+    starts: &'static [u8; 64],
+}
+
+impl GeneticCode {
+
+    /// Translation table with the given NCBI identifier
+    ///
+    /// # Arguments
+    /// * `id` - NCBI table identifier, one of 1-6, 9-16 and 21-33
+    pub fn new(id: usize) -> Result<GeneticCode> {
+        GENETIC_CODES.iter()
+            .find(|&&(table_id, _, _, _)| table_id == id)
+            .map(|&(id, name, amino_acids, starts)| GeneticCode { id, name, amino_acids, starts })
+            .ok_or(BioError::ItemNotFound)
+    }
+
+    /// Standard genetic code (table 1)
+    pub fn standard() -> GeneticCode {
+        GeneticCode::new(1).unwrap()
+    }
+
+    /// Identifiers of all available tables
+    pub fn ids() -> Vec<usize> {
+        GENETIC_CODES.iter().map(|&(id, _, _, _)| id).collect()
+    }
+
+    /// NCBI identifier of the table
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// NCBI name of the table
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Amino acid encoded by a codon, `*` for stop codons
+    ///
+    /// Returns `None` if the codon is not of length 3 or contains letters other than A, C, G, T/U.
+    pub fn translate_codon(&self, codon: &[u8]) -> Option<u8> {
+        codon_index(codon).map(|i| self.amino_acids[i])
+    }
+
+    /// Check whether a codon may initiate translation
+    pub fn is_start(&self, codon: &[u8]) -> bool {
+        codon_index(codon).is_some_and(|i| self.starts[i] == b'M')
+    }
+
+    /// Check whether a codon terminates translation
+    pub fn is_stop(&self, codon: &[u8]) -> bool {
+        codon_index(codon).is_some_and(|i| self.amino_acids[i] == b'*')
+    }
+
+    /// Start codons of the table in DNA alphabet
+    pub fn start_codons(&self) -> Vec<Sequence> {
+        (0..64).filter(|&i| self.starts[i] == b'M').map(codon).collect()
+    }
+
+    /// Stop codons of the table in DNA alphabet
+    pub fn stop_codons(&self) -> Vec<Sequence> {
+        (0..64).filter(|&i| self.amino_acids[i] == b'*').map(codon).collect()
+    }
+
+    /// Translate all codons of a sequence in the first reading frame
+    ///
+    /// Stop codons are translated to `*` and codons containing ambiguous letters to `X`,
+    /// trailing nucleotides not forming a complete codon are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use biogarden::processing::genetic_code::GeneticCode;
+    /// use biogarden::ds::sequence::Sequence;
+    ///
+    /// let code = GeneticCode::new(11).unwrap();
+    /// let protein = code.translate(&Sequence::from("ATGGCNTGATT"));
+    /// assert_eq!(protein, Sequence::from("MX*"));
+    /// ```
+    pub fn translate(&self, seq: &Sequence) -> Sequence {
+        seq.chain
+            .chunks_exact(3)
+            .map(|c| self.translate_codon(c).unwrap_or(b'X'))
+            .collect()
+    }
+}
+
+impl Default for GeneticCode {
+    fn default() -> Self {
+        GeneticCode::standard()
+    }
+}
+
+// Position of a codon within the NCBI tables, nucleotides ordered T, C, A, G
+fn codon_index(codon: &[u8]) -> Option<usize> {
+    if codon.len() != 3 {
+        return None;
+    }
+    codon.iter().try_fold(0, |index, base| {
+        let code = match base {
+            b'T' | b't' | b'U' | b'u' => 0,
+            b'C' | b'c' => 1,
+            b'A' | b'a' => 2,
+            b'G' | b'g' => 3,
+            _ => return None,
+        };
+        Some(4 * index + code)
+    })
+}
+
+// Codon at the given position of the NCBI tables
+fn codon(index: usize) -> Sequence {
+    [index / 16, index / 4 % 4, index % 4].iter().map(|&i| b"TCAG"[i]).collect()
+}
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr, Not, Shl, Shr};
+
+use crate::ds::sequence::{Sequence, Strand};
+use crate::ds::tile::Tile;
+use crate::error::{BioError, Result};
+
+/// Integer type holding a k-mer packed with 2 bits per nucleotide
+///
+/// Implemented for `u64` (k ≤ 32) and `u128` (k ≤ 64).
+/// Nucleotides are encoded as A = 0, C = 1, G = 2, T = 3, the first nucleotide occupying the most significant bits.
+/// Complementary bases sum up to 3, such that complementing a k-mer is a bitwise negation.
+pub trait KmerKey:
+    Copy + Eq + Ord + Hash + Debug + From<u8>
+    + Shl<usize, Output = Self> + Shr<usize, Output = Self>
+    + BitAnd<Output = Self> + BitOr<Output = Self> + Not<Output = Self>
+{
+    /// Number of bits of the underlying integer type
+    const BITS: usize;
+
+    /// Longest k-mer that can be packed into the key
+    fn max_k() -> usize {
+        Self::BITS / 2
+    }
+
+    /// Mask selecting the lowest `2 * k` bits of a key
+    fn mask(k: usize) -> Self {
+        !Self::from(0) >> (Self::BITS - 2 * k)
+    }
+
+    /// Code of the nucleotide stored in the lowest two bits
+    fn low_code(self) -> u8;
+}
+
+impl KmerKey for u64 {
+    const BITS: usize = 64;
+
+    fn low_code(self) -> u8 {
+        (self & 3) as u8
+    }
+}
+
+impl KmerKey for u128 {
+    const BITS: usize = 128;
+
+    fn low_code(self) -> u8 {
+        (self & 3) as u8
+    }
+}
+
+/// Map nucleotide onto its 2-bit code, `None` for letters other than A, C, G, T/U (case-insensitive)
+pub fn encode_base(base: u8) -> Option<u8> {
+    match base {
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' | b'U' | b'u' => Some(3),
+        _ => None,
+    }
+}
+
+/// Pack a k-mer into a key, `None` if it contains ambiguous letters or is too long for the key type
+///
+/// # Example
+/// ```
+/// use biogarden::processing::kmers::{encode_kmer, decode_kmer};
+/// use biogarden::ds::sequence::Sequence;
+///
+/// let key: u64 = encode_kmer(b"GATTACA").unwrap();
+/// assert_eq!(key, 0b10_00_11_11_00_01_00);
+/// assert_eq!(decode_kmer(key, 7), Sequence::from("GATTACA"));
+/// assert_eq!(encode_kmer::<u64>(b"GATNACA"), None);
+/// ```
+pub fn encode_kmer<K: KmerKey>(kmer: &[u8]) -> Option<K> {
+    if kmer.len() > K::max_k() {
+        return None;
+    }
+    kmer.iter().try_fold(K::from(0), |key, &base| Some((key << 2) | K::from(encode_base(base)?)))
+}
+
+/// Unpack a key into a k-mer of length `k`
+pub fn decode_kmer<K: KmerKey>(key: K, k: usize) -> Sequence {
+    (0..k).rev().map(|i| b"ACGT"[(key >> (2 * i)).low_code() as usize]).collect()
+}
+
+/// Reverse complement of a packed k-mer of length `k`
+pub fn reverse_complement_kmer<K: KmerKey>(key: K, k: usize) -> K {
+    let mut rc = K::from(0);
+    let mut key = !key;
+    for _ in 0..k {
+        rc = (rc << 2) | K::from(key.low_code());
+        key = key >> 2;
+    }
+    rc
+}
+
+/// Canonical form of a packed k-mer of length `k`: the smaller of the k-mer and its reverse complement
+pub fn canonical_kmer<K: KmerKey>(key: K, k: usize) -> K {
+    std::cmp::min(key, reverse_complement_kmer(key, k))
+}
+
+/// K-mer of a sequence packed together with its reverse complement, see `CanonicalKmers`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedKmer<K: KmerKey = u64> {
+    /// Start of the k-mer within the sequence
+    pub position: usize,
+    /// K-mer as read from the sequence
+    pub forward: K,
+    /// Reverse complement of the k-mer
+    pub reverse: K,
+}
+
+impl<K: KmerKey> PackedKmer<K> {
+
+    /// Smaller of the k-mer and its reverse complement
+    pub fn canonical(&self) -> K {
+        std::cmp::min(self.forward, self.reverse)
+    }
+
+    /// Strand the canonical k-mer is read from, `Forward` for k-mers equal to their reverse complement
+    pub fn strand(&self) -> Strand {
+        if self.forward <= self.reverse { Strand::Forward } else { Strand::Reverse }
+    }
+
+    /// K-mer equals its reverse complement, i.e. its strand cannot be determined
+    pub fn is_palindrome(&self) -> bool {
+        self.forward == self.reverse
+    }
+}
+
+/// Iterator over the k-mers of a sequence together with their reverse complements
+///
+/// Complexity: O(n)
+///
+/// Both orientations are rolled along the sequence in a single pass, shifting in 2 bits per letter.
+/// One item is yielded for every k-mer, such that the index of an item is the start of its k-mer.
+/// K-mers containing letters other than A, C, G, T/U are yielded as `None`.
+///
+/// # Example
+/// ```
+/// use biogarden::processing::kmers::{CanonicalKmers, encode_kmer};
+/// use biogarden::ds::sequence::{Sequence, Strand};
+///
+/// let seq = Sequence::from("GATNACGT");
+/// let kmers: Vec<_> = CanonicalKmers::<u64>::new(&seq, 3).unwrap().collect();
+/// assert_eq!(kmers.len(), 6);
+/// assert!(kmers[1..4].iter().all(|kmer| kmer.is_none()));
+///
+/// // GAT is read on the forward strand, ACG is the reverse complement of CGT
+/// let (gat, cgt) = (kmers[0].unwrap(), kmers[5].unwrap());
+/// assert_eq!((gat.canonical(), gat.strand()), (encode_kmer(b"ATC").unwrap(), Strand::Reverse));
+/// assert_eq!((cgt.canonical(), cgt.strand()), (encode_kmer(b"ACG").unwrap(), Strand::Reverse));
+/// assert_eq!(kmers[4].unwrap().canonical(), encode_kmer(b"ACG").unwrap());
+/// ```
+pub struct CanonicalKmers<'a, K: KmerKey = u64> {
+    seq: &'a Sequence,
+    k: usize,
+    mask: K,
+    pos: usize,
+    // Number of consecutive valid letters ending at the current position
+    valid: usize,
+    forward: K,
+    reverse: K,
+}
+
+impl<'a, K: KmerKey> CanonicalKmers<'a, K> {
+
+    /// Iterate over the k-mers of `seq`, fails with `InvalidArgumentRange` unless `0 < k <= K::max_k()`
+    pub fn new(seq: &'a Sequence, k: usize) -> Result<Self> {
+        if k == 0 || k > K::max_k() {
+            return Err(BioError::InvalidArgumentRange);
+        }
+        Ok(CanonicalKmers { seq, k, mask: K::mask(k), pos: 0, valid: 0, forward: K::from(0), reverse: K::from(0) })
+    }
+}
+
+impl<K: KmerKey> Iterator for CanonicalKmers<'_, K> {
+    type Item = Option<PackedKmer<K>>;
+
+    fn next(&mut self) -> Option<Option<PackedKmer<K>>> {
+        // Fill window until the first k-mer is complete
+        while self.pos < self.seq.len() {
+            let base = self.seq[self.pos];
+            self.pos += 1;
+            match encode_base(base) {
+                Some(code) => {
+                    // Roll forward k-mer to the left and its reverse complement to the right
+                    self.forward = ((self.forward << 2) | K::from(code)) & self.mask;
+                    self.reverse = (self.reverse >> 2) | (K::from(3 - code) << (2 * (self.k - 1)));
+                    self.valid += 1;
+                }
+                None => self.valid = 0,
+            }
+            if self.pos < self.k {
+                continue;
+            }
+            if self.valid < self.k {
+                return Some(None);
+            }
+            return Some(Some(PackedKmer { position: self.pos - self.k, forward: self.forward, reverse: self.reverse }));
+        }
+        None
+    }
+}
+
+/// Count k-mers of DNA sequences in a hash table with 2-bit packed keys
+///
+/// In contrast to `k_mer_composition`, memory is proportional to the number of distinct k-mers present in the input,
+/// allowing for k up to 32 with `u64` keys and up to 64 with `u128` keys.
+/// In canonical mode a k-mer and its reverse complement are counted together,
+/// making the counts independent of the strand sequenced. Windows containing letters other than A, C, G, T/U are skipped.
+///
+/// # Example
+/// ```
+/// use biogarden::processing::kmers::KmerCounter;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// let mut counter = KmerCounter::<u64>::new(3, true).unwrap();
+/// counter.count_sequence(&Sequence::from("AAACGTTTNAAA"));
+///
+/// // AAA, TTT (reverse complement of AAA) and AAA again
+/// assert_eq!(counter.get(b"AAA"), 3);
+/// assert_eq!(counter.get(b"TTT"), 3);
+/// // ACG and its reverse complement CGT
+/// assert_eq!(counter.get(b"ACG"), 2);
+/// // Distinct canonical k-mers: AAA, AAC (GTT), ACG (CGT)
+/// assert_eq!(counter.len(), 3);
+/// // Two k-mers occur twice, one three times
+/// assert_eq!(counter.histogram(), vec![0, 0, 2, 1]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct KmerCounter<K: KmerKey = u64> {
+    k: usize,
+    canonical: bool,
+    counts: HashMap<K, usize>,
+}
+
+impl<K: KmerKey> KmerCounter<K> {
+
+    /// Construct an empty counter of k-mers of length `k`
+    ///
+    /// # Arguments
+    /// * `k` - length of the counted k-mers, at most 32 for `u64` keys and 64 for `u128` keys
+    /// * `canonical` - count k-mers together with their reverse complements
+    pub fn new(k: usize, canonical: bool) -> Result<KmerCounter<K>> {
+        if k == 0 || k > K::max_k() {
+            return Err(BioError::InvalidArgumentRange);
+        }
+        Ok(KmerCounter { k, canonical, counts: HashMap::new() })
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Number of distinct k-mers
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Count all k-mers of a sequence
+    pub fn count_sequence(&mut self, seq: &Sequence) {
+        // Length of k-mers has been validated on construction
+        for kmer in CanonicalKmers::<K>::new(seq, self.k).unwrap().flatten() {
+            let key = if self.canonical { kmer.canonical() } else { kmer.forward };
+            *self.counts.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    /// Count k-mers of all sequences in a tile
+    pub fn count_tile(&mut self, tile: &Tile) {
+        for seq in tile {
+            self.count_sequence(seq);
+        }
+    }
+
+    /// Add counts of another counter, e.g. obtained from a different tile
+    ///
+    /// Both counters must count k-mers of the same length in the same mode.
+    pub fn merge(&mut self, other: &KmerCounter<K>) -> Result<()> {
+        if self.k != other.k || self.canonical != other.canonical {
+            return Err(BioError::InvalidArgumentRange);
+        }
+        for (&key, &count) in &other.counts {
+            *self.counts.entry(key).or_insert(0) += count;
+        }
+        Ok(())
+    }
+
+    /// Number of occurrences of a k-mer, in canonical mode including its reverse complement
+    pub fn get(&self, kmer: &[u8]) -> usize {
+        if kmer.len() != self.k {
+            return 0;
+        }
+        match encode_kmer::<K>(kmer) {
+            Some(key) => self.get_key(key),
+            None => 0,
+        }
+    }
+
+    /// Number of occurrences of a packed k-mer, in canonical mode including its reverse complement
+    pub fn get_key(&self, key: K) -> usize {
+        let key = if self.canonical { canonical_kmer(key, self.k) } else { key };
+        self.counts.get(&key).cloned().unwrap_or(0)
+    }
+
+    /// Iterate over packed k-mers and their counts, in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &usize)> {
+        self.counts.iter()
+    }
+
+    /// Remove k-mers occurring less than `min_count` times, e.g. to discard sequencing errors
+    pub fn filter_min_count(&mut self, min_count: usize) {
+        self.counts.retain(|_, count| *count >= min_count);
+    }
+
+    /// Unpacked k-mers with their counts, sorted lexicographically
+    pub fn kmers(&self) -> Vec<(Sequence, usize)> {
+        let mut keys: Vec<(&K, &usize)> = self.counts.iter().collect();
+        keys.sort_unstable();
+        keys.into_iter().map(|(&key, &count)| (decode_kmer(key, self.k), count)).collect()
+    }
+
+    /// K-mer spectrum: entry `i` holds the number of distinct k-mers occurring exactly `i` times
+    pub fn histogram(&self) -> Vec<usize> {
+        let max_count = self.counts.values().cloned().max().unwrap_or(0);
+        let mut histogram = vec![0; max_count + 1];
+        for &count in self.counts.values() {
+            histogram[count] += 1;
+        }
+        histogram
+    }
+}
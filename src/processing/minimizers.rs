@@ -0,0 +1,210 @@
+use std::collections::VecDeque;
+
+use crate::ds::sequence::{Sequence, Strand};
+use crate::error::{BioError, Result};
+use crate::processing::kmers::{CanonicalKmers, KmerKey, PackedKmer};
+
+/// K-mer selected as a seed from a sequence, see `minimizers` and `syncmers`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Seed {
+    /// Hash of the canonical k-mer
+    pub hash: u64,
+    /// Canonical k-mer packed with 2 bits per nucleotide, see `processing::kmers`
+    pub kmer: u64,
+    /// Start of the k-mer within the sequence
+    pub position: usize,
+    /// Strand the canonical k-mer has been read from, `Forward` for k-mers equal to their reverse complement
+    pub strand: Strand,
+}
+
+/// Invertible integer hash of a packed k-mer, restricted to the bits selected by `mask`
+///
+/// Hashing avoids the bias of lexicographic ordering towards poly-A k-mers,
+/// while being invertible guarantees that distinct k-mers receive distinct hashes.
+pub fn hash_kmer(key: u64, mask: u64) -> u64 {
+    let mut key = (!key).wrapping_add(key << 21) & mask;
+    key ^= key >> 24;
+    key = key.wrapping_add(key << 3).wrapping_add(key << 8) & mask;
+    key ^= key >> 14;
+    key = key.wrapping_add(key << 2).wrapping_add(key << 4) & mask;
+    key ^= key >> 28;
+    key.wrapping_add(key << 31) & mask
+}
+
+// Seed of a canonical k-mer, hashed with `hash_kmer`
+fn seed(kmer: PackedKmer, k: usize) -> Seed {
+    let key = kmer.canonical();
+    Seed { hash: hash_kmer(key, u64::mask(k)), kmer: key, position: kmer.position, strand: kmer.strand() }
+}
+
+// Sliding window minimum over a stream of values, the deque holds candidates with non-decreasing values
+struct WindowMinimum<T: Copy> {
+    window: usize,
+    deque: VecDeque<(usize, u64, T)>,
+}
+
+impl<T: Copy> WindowMinimum<T> {
+    fn new(window: usize) -> Self {
+        WindowMinimum { window, deque: VecDeque::new() }
+    }
+
+    fn clear(&mut self) {
+        self.deque.clear();
+    }
+
+    // Insert value at index `i`, returns minimum of window ending at `i`
+    fn push(&mut self, i: usize, value: u64, item: T) -> (usize, u64, T) {
+        while matches!(self.deque.back(), Some(&(_, v, _)) if v > value) {
+            self.deque.pop_back();
+        }
+        self.deque.push_back((i, value, item));
+        while matches!(self.deque.front(), Some(&(j, _, _)) if j + self.window <= i) {
+            self.deque.pop_front();
+        }
+        *self.deque.front().unwrap()
+    }
+}
+
+/// Extract (w,k)-minimizers of a sequence
+///
+/// From every window of `w` consecutive k-mers the k-mer with the smallest hash is selected, ties (repeated k-mers) are all selected.
+/// Consecutive windows frequently share their minimizer, which is reported only once, resulting in a sparse set of seeds
+/// with the guarantee that any two sequences sharing a substring of length `w + k - 1` share a minimizer.
+/// K-mers are canonical, therefore the same minimizers are selected on both strands of the sequence.
+/// Windows overlapping letters other than A, C, G, T/U or palindromic k-mers are skipped.
+///
+/// # Arguments
+/// * `seq` - DNA sequence
+/// * `w` - number of consecutive k-mers in a window
+/// * `k` - length of k-mers, at most 32
+///
+/// # Example
+/// ```
+/// use biogarden::processing::minimizers::minimizers;
+/// use biogarden::processing::transformers::complement_dna;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// let seq = Sequence::from("CAGTTGACCATGCAATTGCACCGATTACGGATCAGCTAGGCA");
+/// let forward = minimizers(&seq, 5, 7).unwrap();
+/// let reverse = minimizers(&complement_dna(seq.clone()), 5, 7).unwrap();
+///
+/// // Every window of 5 k-mers contains a minimizer
+/// assert!(forward.windows(2).all(|m| m[1].position - m[0].position <= 5));
+/// // Same k-mers are selected on the opposite strand, at mirrored positions
+/// assert_eq!(forward.len(), reverse.len());
+/// for (f, r) in forward.iter().zip(reverse.iter().rev()) {
+///     assert_eq!(f.kmer, r.kmer);
+///     assert_eq!(f.position, seq.len() - 7 - r.position);
+///     assert_ne!(f.strand, r.strand);
+/// }
+/// ```
+pub fn minimizers(seq: &Sequence, w: usize, k: usize) -> Result<Vec<Seed>> {
+    if w == 0 {
+        return Err(BioError::InvalidArgumentRange);
+    }
+    // Strand of palindromic k-mers cannot be determined
+    let kmers = CanonicalKmers::<u64>::new(seq, k)?
+        .map(|kmer| kmer.filter(|kmer| !kmer.is_palindrome()).map(|kmer| seed(kmer, k)));
+
+    let mut seeds = Vec::<Seed>::new();
+    let mut window = WindowMinimum::<Seed>::new(w);
+    // Number of consecutive valid k-mers ending at the current one
+    let mut run = 0_usize;
+
+    for (i, kmer) in kmers.enumerate() {
+        let kmer = match kmer {
+            Some(kmer) => kmer,
+            None => {
+                run = 0;
+                window.clear();
+                continue;
+            }
+        };
+        run += 1;
+        let (_, min_hash, _) = window.push(i, kmer.hash, kmer);
+        if run < w {
+            continue;
+        }
+        // Report all k-mers sharing the minimum hash, keeping the selection independent of the strand
+        for &(_, _, minimizer) in window.deque.iter().take_while(|&&(_, hash, _)| hash == min_hash) {
+            if seeds.last().is_none_or(|s| s.position < minimizer.position) {
+                seeds.push(minimizer);
+            }
+        }
+    }
+    Ok(seeds)
+}
+
+/// Extract syncmers of a sequence, k-mers selected by the position of their smallest s-mer
+///
+/// Each k-mer is inspected in isolation: its `k - s + 1` s-mers are hashed and the k-mer becomes a seed,
+/// if the smallest s-mer starts at one of the given `offsets` within the k-mer.
+/// Since the selection does not depend on the context of a k-mer, a mutation only affects seeds overlapping it.
+/// Open syncmers use a single offset (typically 0), closed syncmers use both the first and the last offset (see `closed_syncmers`).
+/// S-mers are canonical, and so are the selected k-mers.
+///
+/// # Arguments
+/// * `seq` - DNA sequence
+/// * `k` - length of k-mers, at most 32
+/// * `s` - length of s-mers, smaller than or equal to `k`
+/// * `offsets` - admissible start positions of the smallest s-mer within the k-mer, in range `0..=k-s`
+///
+/// # Example
+/// ```
+/// use biogarden::processing::minimizers::{syncmers, closed_syncmers};
+/// use biogarden::ds::sequence::Sequence;
+///
+/// let seq = Sequence::from("CAGTTGACCATGCAATTGCACCGATTACGGATCAGCTAGGCA");
+/// let open = syncmers(&seq, 9, 4, &[0]).unwrap();
+/// let closed = closed_syncmers(&seq, 9, 4).unwrap();
+///
+/// // Closed syncmers are a superset of open syncmers with offset 0
+/// assert!(open.iter().all(|seed| closed.contains(seed)));
+/// ```
+pub fn syncmers(seq: &Sequence, k: usize, s: usize, offsets: &[usize]) -> Result<Vec<Seed>> {
+    if k == 0 || k > u64::max_k() || s == 0 || s > k || offsets.iter().any(|&t| t > k - s) {
+        return Err(BioError::InvalidArgumentRange);
+    }
+
+    // Pair k-mers with the minimum s-mer of their window, both ending at the same letter
+    let span = k - s + 1;
+    let mut kmers = CanonicalKmers::<u64>::new(seq, k)?.map(|kmer| kmer.map(|kmer| seed(kmer, k)));
+    let mut window = WindowMinimum::<()>::new(span);
+    let mut run = 0_usize;
+    let mut seeds = Vec::<Seed>::new();
+
+    for (i, smer) in CanonicalKmers::<u64>::new(seq, s)?.map(|smer| smer.map(|smer| seed(smer, s))).enumerate() {
+        match smer {
+            Some(smer) => {
+                run += 1;
+                window.push(i, smer.hash, ());
+            }
+            None => {
+                run = 0;
+                window.clear();
+            }
+        };
+        // First k-mer ends together with s-mer at index `k - s`
+        if i + 1 < span {
+            continue;
+        }
+        let kmer = kmers.next().flatten();
+        if let (Some(kmer), true) = (kmer, run >= span) {
+            let (min_pos, _, _) = window.deque.front().cloned().unwrap();
+            if offsets.contains(&(min_pos - kmer.position)) {
+                seeds.push(kmer);
+            }
+        }
+    }
+    Ok(seeds)
+}
+
+/// Extract closed syncmers of a sequence, k-mers whose smallest s-mer is located at their start or end
+///
+/// See `syncmers` for details, closed syncmers guarantee that every window of `k - s` consecutive k-mers contains at least one seed.
+pub fn closed_syncmers(seq: &Sequence, k: usize, s: usize) -> Result<Vec<Seed>> {
+    if s > k {
+        return Err(BioError::InvalidArgumentRange);
+    }
+    syncmers(seq, k, s, &[0, k - s])
+}
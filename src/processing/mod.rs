@@ -2,5 +2,13 @@
 pub mod patterns;
 /// Transcription, complements and error correction of genetic strings
 pub mod transformers;
+/// Counting of 2-bit packed and canonical k-mers
+pub mod kmers;
+/// Minimizer and syncmer seed extraction
+pub mod minimizers;
+/// NCBI genetic codes for translation of codons
+pub mod genetic_code;
+/// Open reading frames and six-frame translation
+pub mod orf;
 
 mod constants;
\ No newline at end of file
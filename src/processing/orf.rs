@@ -0,0 +1,264 @@
+use crate::ds::sequence::{Sequence, Strand};
+use crate::processing::genetic_code::GeneticCode;
+use crate::processing::transformers::reverse_complement;
+
+/// Open reading frame located by `OrfFinder`
+///
+/// Coordinates refer to the forward strand of the searched sequence (0-based, end exclusive),
+/// also for ORFs on the reverse strand, and include the stop codon if present.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orf {
+    /// First nucleotide of the ORF on the forward strand
+    pub start: usize,
+    /// Position following the last nucleotide of the ORF on the forward strand
+    pub end: usize,
+    /// Strand the ORF is read from
+    pub strand: Strand,
+    /// Reading frame, the offset (0, 1 or 2) of the first codon from the 5' end of the strand
+    pub frame: usize,
+    /// Translated protein, excluding the stop codon
+    pub protein: Sequence,
+    /// ORF begins with a start codon, otherwise it is truncated at the 5' end of the sequence
+    pub has_start: bool,
+    /// ORF ends with a stop codon, otherwise it is truncated at the 3' end of the sequence
+    pub has_stop: bool,
+}
+
+impl Orf {
+    /// Check whether the ORF is bounded by a start and a stop codon
+    pub fn is_complete(&self) -> bool {
+        self.has_start && self.has_stop
+    }
+
+    /// Length of the ORF in nucleotides
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Policy for start codons located within an ORF sharing the same stop codon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NestedOrfs {
+    /// Report only the longest ORF per stop codon, beginning at the most upstream start codon
+    Longest,
+    /// Report an ORF for every start codon
+    All,
+}
+
+/// Search open reading frames in all six reading frames of a DNA sequence
+///
+/// An ORF spans from a start codon to the next in-frame stop codon of the genetic code.
+/// Unlike `open_reading_frames`, ORFs are reported with their coordinates, strand and frame.
+/// Optionally, ORFs truncated by the ends of the sequence are reported (e.g. genes overlapping contig boundaries),
+/// a 5' truncated ORF spans from the first codon of its frame and does not require a start codon.
+///
+/// Defaults: standard genetic code, `AUG` as the only start codon, minimum protein length of 1,
+/// longest ORF per stop codon only, no truncated ORFs.
+///
+/// # Example
+/// ```
+/// use biogarden::processing::orf::{OrfFinder, NestedOrfs};
+/// use biogarden::ds::sequence::{Sequence, Strand};
+///
+/// // Forward strand: ATG AAA ATG CCC GGG TAA, reverse strand: ATG TTG TAA
+/// let dna = Sequence::from("CCATGAAAATGCCCGGGTAATTACAACATCC");
+///
+/// let orfs = OrfFinder::new().find(&dna);
+/// assert_eq!(orfs.len(), 2);
+/// assert_eq!((orfs[0].start, orfs[0].end, orfs[0].strand), (2, 20, Strand::Forward));
+/// assert_eq!(orfs[0].protein, Sequence::from("MKMPG"));
+/// assert_eq!((orfs[1].start, orfs[1].end, orfs[1].strand), (20, 29, Strand::Reverse));
+/// assert_eq!(orfs[1].protein, Sequence::from("ML"));
+///
+/// // Nested start codons and minimum length
+/// let orfs = OrfFinder::new().with_nested(NestedOrfs::All).with_min_len(4).find(&dna);
+/// assert_eq!(orfs.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrfFinder {
+    code: GeneticCode,
+    min_len: usize,
+    alternative_starts: bool,
+    nested: NestedOrfs,
+    partial: bool,
+}
+
+impl OrfFinder {
+
+    pub fn new() -> OrfFinder {
+        OrfFinder {
+            code: GeneticCode::standard(),
+            min_len: 1,
+            alternative_starts: false,
+            nested: NestedOrfs::Longest,
+            partial: false,
+        }
+    }
+
+    /// Genetic code used to identify start and stop codons and translate ORFs
+    pub fn with_genetic_code(mut self, code: GeneticCode) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Discard ORFs encoding proteins shorter than `min_len` amino acids
+    pub fn with_min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len;
+        self
+    }
+
+    /// Initiate ORFs at all start codons of the genetic code, instead of `ATG` only
+    pub fn with_alternative_starts(mut self, alternative_starts: bool) -> Self {
+        self.alternative_starts = alternative_starts;
+        self
+    }
+
+    /// Policy for ORFs nested within longer ORFs of the same frame
+    pub fn with_nested(mut self, nested: NestedOrfs) -> Self {
+        self.nested = nested;
+        self
+    }
+
+    /// Report ORFs lacking a start or stop codon, because they extend beyond the sequence
+    pub fn with_partial(mut self, partial: bool) -> Self {
+        self.partial = partial;
+        self
+    }
+
+    /// Find ORFs on both strands of a DNA or RNA sequence, sorted by their start position, soft-masked letters included
+    pub fn find(&self, dna: &Sequence) -> Vec<Orf> {
+        let mut orfs = Vec::<Orf>::new();
+        for (strand, seq) in [(Strand::Forward, dna.clone()), (Strand::Reverse, reverse_complement(dna))] {
+            for frame in 0..3 {
+                self.find_in_frame(&seq, strand, frame, &mut orfs);
+            }
+        }
+        orfs.sort_by_key(|orf| (orf.start, orf.end));
+        orfs
+    }
+
+    // Scan codons of a single frame, keeping track of start codons awaiting their stop codon
+    fn find_in_frame(&self, seq: &Sequence, strand: Strand, frame: usize, orfs: &mut Vec<Orf>) {
+        // Positions of pending starts, flagged if located at a start codon
+        let mut open = Vec::<(usize, bool)>::new();
+        if self.partial {
+            open.push((frame, false));
+        }
+
+        let mut pos = frame;
+        while pos + 3 <= seq.len() {
+            let codon = &seq[pos..pos + 3];
+            if self.code.is_stop(codon) {
+                self.report(seq, strand, frame, &open, pos + 3, true, orfs);
+                open.clear();
+            } else if self.is_start(codon) {
+                open.push((pos, true));
+            }
+            pos += 3;
+        }
+        if self.partial {
+            self.report(seq, strand, frame, &open, pos, false, orfs);
+        }
+    }
+
+    // Emit ORFs of pending starts ending at `end` (strand coordinates)
+    #[allow(clippy::too_many_arguments)]
+    fn report(&self, seq: &Sequence, strand: Strand, frame: usize, open: &[(usize, bool)],
+              end: usize, has_stop: bool, orfs: &mut Vec<Orf>) {
+        let selected = match self.nested {
+            NestedOrfs::Longest => &open[..open.len().min(1)],
+            NestedOrfs::All => open,
+        };
+        for &(start, has_start) in selected {
+            let coding_end = if has_stop { end - 3 } else { end };
+            let mut protein = self.code.translate(&Sequence::from(&seq[start..coding_end]));
+            // Alternative start codons initiate translation with methionine
+            if has_start {
+                protein.chain[0] = b'M';
+            }
+            if protein.len() < self.min_len {
+                continue;
+            }
+            let (start_fwd, end_fwd) = match strand {
+                Strand::Forward => (start, end),
+                Strand::Reverse => (seq.len() - end, seq.len() - start),
+            };
+            orfs.push(Orf { start: start_fwd, end: end_fwd, strand, frame, protein, has_start, has_stop });
+        }
+    }
+
+    fn is_start(&self, codon: &[u8]) -> bool {
+        if self.alternative_starts {
+            self.code.is_start(codon)
+        } else {
+            codon.eq_ignore_ascii_case(b"ATG") || codon.eq_ignore_ascii_case(b"AUG")
+        }
+    }
+}
+
+impl Default for OrfFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reading frame translated by `six_frame_translation`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranslatedFrame {
+    /// Strand the frame is read from
+    pub strand: Strand,
+    /// Offset (0, 1 or 2) of the first codon from the 5' end of the strand
+    pub frame: usize,
+    /// Translated codons, stop codons as `*`, codons with ambiguous letters as `X`
+    pub protein: Sequence,
+}
+
+impl TranslatedFrame {
+    /// Coordinates on the forward strand (0-based, end exclusive) of the codon translated into the `i`-th amino acid
+    pub fn codon_range(&self, i: usize, seq_len: usize) -> (usize, usize) {
+        let start = self.frame + 3 * i;
+        match self.strand {
+            Strand::Forward => (start, start + 3),
+            Strand::Reverse => (seq_len - start - 3, seq_len - start),
+        }
+    }
+}
+
+/// Translate a DNA sequence in all six reading frames
+///
+/// Frames are returned in the order +0, +1, +2 (forward strand) and -0, -1, -2 (reverse strand),
+/// coordinates of individual codons can be recovered with `TranslatedFrame::codon_range`.
+///
+/// # Arguments
+/// * `dna` - DNA sequence to translate
+/// * `code` - genetic code, e.g. `GeneticCode::standard()`
+///
+/// # Example
+/// ```
+/// use biogarden::processing::orf::six_frame_translation;
+/// use biogarden::processing::genetic_code::GeneticCode;
+/// use biogarden::ds::sequence::{Sequence, Strand};
+///
+/// let dna = Sequence::from("ATGGCCTGAAC");
+/// let frames = six_frame_translation(&dna, &GeneticCode::standard());
+///
+/// assert_eq!(frames[0].protein, Sequence::from("MA*"));
+/// assert_eq!(frames[3].strand, Strand::Reverse);
+/// assert_eq!(frames[3].protein, Sequence::from("VQA"));
+/// // Alanine of the reverse frame is encoded by the reverse complement of GGC at positions 2..5
+/// assert_eq!(frames[3].codon_range(2, dna.len()), (2, 5));
+/// ```
+pub fn six_frame_translation(dna: &Sequence, code: &GeneticCode) -> Vec<TranslatedFrame> {
+    let mut frames = Vec::<TranslatedFrame>::new();
+    for (strand, seq) in [(Strand::Forward, dna.clone()), (Strand::Reverse, reverse_complement(dna))] {
+        for frame in 0..3 {
+            let protein = code.translate(&Sequence::from(&seq[frame.min(seq.len())..]));
+            frames.push(TranslatedFrame { strand, frame, protein });
+        }
+    }
+    frames
+}
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::ops::Range;
 
 use crate::ds::builders::suffix_tree::SuffixTreeBuilder;
-use crate::ds::sequence::Sequence;
+use crate::ds::builders::trie::Trie;
+use crate::ds::sequence::{Sequence, Strand};
 use crate::ds::tile::Tile;
 use crate::error::{BioError, Result};
 
@@ -58,6 +60,302 @@ pub fn find_motif(seq: &Sequence, pat: &Sequence) -> Vec<usize> {
     ret
 }
 
+/// Return all positions at which each of a set of motifs occurs in a genetic sequence
+///
+/// Complexity: O(n + m + z), with `m` - total length of motifs, `z` - number of occurrences
+///
+/// Screening reads against large collections of adapters or barcodes with `find_motif` requires a full pass per motif.
+/// Instead, the motifs are inserted into a keyword trie that is extended into an Aho-Corasick automaton,
+/// such that all occurrences of all motifs are reported in a single pass over the sequence.
+///  
+/// # Arguments
+/// * `seq` - genetic sequence to search for motifs
+/// * `patterns` - container holding the motifs that have to be found
+/// * `alphabet` - letters the motifs are composed of, other letters in `seq` never match
+///
+/// # Example
+/// ```
+/// use biogarden::processing::patterns::find_motifs;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// let genome = Sequence::from("GATATATGCATATACTT");
+/// let mut motifs = Tile::new();
+/// motifs.push(Sequence::from("ATAT"));
+/// motifs.push(Sequence::from("TATA"));
+/// motifs.push(Sequence::from("GCA"));
+///
+/// let pos = find_motifs(&genome, &motifs, &[b'A', b'C', b'G', b'T']).unwrap();
+/// assert_eq!(pos, [vec![1, 3, 9], vec![2, 10], vec![7]]);
+/// ```
+pub fn find_motifs(seq: &Sequence, patterns: &Tile, alphabet: &[u8]) -> Result<Vec<Vec<usize>>> {
+    // Empty motifs would match at every position
+    if patterns.into_iter().any(|p| p.is_empty()) {
+        return Err(BioError::InvalidInputSize);
+    }
+
+    // Build Aho-Corasick automaton over all motifs
+    let mut trie = Trie::new(alphabet);
+    trie.build(patterns)?;
+
+    // Scan sequence once and distribute occurrences to motifs
+    let mut positions = vec![Vec::<usize>::new(); patterns.len()];
+    for (word_id, start) in trie.find_all(seq)? {
+        positions[word_id].push(start);
+    }
+    Ok(positions)
+}
+
+/// Compiled sequence pattern in IUPAC or PROSITE notation
+///
+/// Each position of the pattern is compiled into the set of letters it accepts,
+/// together with the number of times it may be repeated. 
+/// Nucleotide patterns (IUPAC) treat `T` and `U` as equivalent and can be reverse complemented,
+/// such that both strands of DNA can be searched. Matching is case-insensitive.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    elements: Vec<PatternElement>,
+    // Match must begin at the first position of the sequence (PROSITE `<`)
+    anchored_start: bool,
+    // Match must end at the last position of the sequence (PROSITE `>`)
+    anchored_end: bool,
+    // Pattern was compiled from nucleotide codes
+    nucleotide: bool,
+}
+
+// Single position of a compiled pattern, repeated `min..=max` times
+#[derive(Debug, Clone)]
+struct PatternElement {
+    allowed: [bool; 256],
+    min: usize,
+    max: usize,
+}
+
+impl PatternElement {
+
+    fn new(letters: &[u8], min: usize, max: usize) -> PatternElement {
+        let mut allowed = [false; 256];
+        for &c in letters {
+            allowed[c.to_ascii_uppercase() as usize] = true;
+            allowed[c.to_ascii_lowercase() as usize] = true;
+        }
+        PatternElement { allowed, min, max }
+    }
+
+    fn letters(&self) -> Vec<u8> {
+        (b'A'..=b'Z').filter(|&c| self.allowed[c as usize]).collect()
+    }
+}
+
+impl Pattern {
+
+    /// Compile a nucleotide pattern written in IUPAC ambiguity codes, eg. `TATAWAWR`
+    pub fn from_iupac(pattern: &str) -> Result<Pattern> {
+        let mut elements = Vec::<PatternElement>::new();
+        for c in pattern.bytes() {
+            let letters: &[u8] = match c.to_ascii_uppercase() {
+                b'A' => b"A",
+                b'C' => b"C",
+                b'G' => b"G",
+                b'T' | b'U' => b"TU",
+                b'R' => b"AG",
+                b'Y' => b"CTU",
+                b'S' => b"CG",
+                b'W' => b"ATU",
+                b'K' => b"GTU",
+                b'M' => b"AC",
+                b'B' => b"CGTU",
+                b'D' => b"AGTU",
+                b'H' => b"ACTU",
+                b'V' => b"ACG",
+                b'N' => b"ACGTU",
+                _ => return Err(BioError::TypeConversionError),
+            };
+            elements.push(PatternElement::new(letters, 1, 1));
+        }
+        if elements.is_empty() {
+            return Err(BioError::InvalidInputSize);
+        }
+        Ok(Pattern { elements, anchored_start: false, anchored_end: false, nucleotide: true })
+    }
+
+    /// Compile a protein pattern written in PROSITE notation, eg. `N-{P}-[ST]-{P}`
+    ///
+    /// Supported are single amino acids, `x` for any amino acid, alternatives `[ST]`, 
+    /// exclusions `{P}`, repetitions `x(2)` or `x(2,4)`, and the anchors `<` and `>`.
+    /// Patterns that can match zero letters, such as `x(0,2)`, fail with `InvalidInputSize`.
+    pub fn from_prosite(pattern: &str) -> Result<Pattern> {
+        let mut body = pattern.trim().trim_end_matches('.');
+        let anchored_start = body.starts_with('<');
+        if anchored_start {
+            body = &body[1..];
+        }
+        let anchored_end = body.ends_with('>');
+        if anchored_end {
+            body = &body[..body.len() - 1];
+        }
+
+        let amino_acids: Vec<u8> = (b'A'..=b'Z').collect();
+        let mut elements = Vec::<PatternElement>::new();
+
+        for token in body.split('-') {
+            let token = token.trim().as_bytes();
+            if token.is_empty() {
+                return Err(BioError::TypeConversionError);
+            }
+
+            // Parse the set of accepted letters
+            let (letters, rest) = match token[0] {
+                b'x' | b'X' => (amino_acids.clone(), &token[1..]),
+                b'[' | b'{' => {
+                    let close = if token[0] == b'[' { b']' } else { b'}' };
+                    let end = token.iter().position(|&c| c == close).ok_or(BioError::TypeConversionError)?;
+                    let listed = &token[1..end];
+                    if listed.is_empty() || !listed.iter().all(u8::is_ascii_alphabetic) {
+                        return Err(BioError::TypeConversionError);
+                    }
+                    let letters = if token[0] == b'[' {
+                        listed.to_vec()
+                    } else {
+                        amino_acids.iter().filter(|c| !listed.contains(c)).cloned().collect()
+                    };
+                    (letters, &token[end + 1..])
+                }
+                c if c.is_ascii_alphabetic() => (vec![c], &token[1..]),
+                _ => return Err(BioError::TypeConversionError),
+            };
+
+            // Parse optional repetition `(n)` or `(n,m)`
+            let (min, max) = if rest.is_empty() {
+                (1, 1)
+            } else {
+                if rest[0] != b'(' || rest[rest.len() - 1] != b')' {
+                    return Err(BioError::TypeConversionError);
+                }
+                let range = std::str::from_utf8(&rest[1..rest.len() - 1]).map_err(|_| BioError::TypeConversionError)?;
+                let bounds: Vec<usize> = range
+                    .split(',')
+                    .map(|x| x.trim().parse::<usize>().map_err(|_| BioError::TypeConversionError))
+                    .collect::<Result<Vec<usize>>>()?;
+                match bounds[..] {
+                    [n] => (n, n),
+                    [n, m] if n <= m => (n, m),
+                    _ => return Err(BioError::InvalidArgumentRange),
+                }
+            };
+            elements.push(PatternElement::new(&letters, min, max));
+        }
+        if elements.iter().all(|e| e.min == 0) {
+            return Err(BioError::InvalidInputSize);
+        }
+
+        Ok(Pattern { elements, anchored_start, anchored_end, nucleotide: false })
+    }
+
+    /// Pattern matching the reverse complement strand, available for nucleotide patterns only
+    pub fn reverse_complement(&self) -> Result<Pattern> {
+        if !self.nucleotide {
+            return Err(BioError::InvalidArgumentRange);
+        }
+        let elements = self.elements.iter().rev().map(|e| {
+            let complement: Vec<u8> = e.letters().iter().flat_map(|c| match c {
+                b'A' => b"TU".to_vec(),
+                b'T' | b'U' => b"A".to_vec(),
+                b'C' => b"G".to_vec(),
+                b'G' => b"C".to_vec(),
+                _ => vec![],
+            }).collect();
+            PatternElement::new(&complement, e.min, e.max)
+        }).collect();
+        Ok(Pattern { elements, anchored_start: self.anchored_end, anchored_end: self.anchored_start, nucleotide: true })
+    }
+
+    /// Return all positions at which the pattern matches `seq`
+    ///
+    /// Complexity: O(nEL^2), with `n` - length of the sequence, `E` - number of pattern elements,
+    /// `L` - maximal number of letters matched by the pattern
+    pub fn find(&self, seq: &Sequence) -> Vec<usize> {
+        // Patterns match at least one letter, so no match begins at the end of the sequence
+        let starts = if self.anchored_start { 0..seq.len().min(1) } else { 0..seq.len() };
+        let span: usize = self.elements.iter().map(|e| e.max).sum();
+        starts
+            .filter(|&pos| self.matches_at(&seq.chain, pos, span))
+            .collect()
+    }
+
+    // Check whether the pattern matches `seq` beginning at `pos`, matching at most `span` letters.
+    // Instead of backtracking over the repetitions of every element, which is exponential in the number
+    // of variable repetitions, the set of end positions reachable after each element is tracked.
+    fn matches_at(&self, seq: &[u8], pos: usize, span: usize) -> bool {
+        let limit = span.min(seq.len() - pos);
+        let mut reachable = vec![0_usize];
+        let mut next = vec![false; limit + 1];
+        for element in &self.elements {
+            let accepts = |i: usize| pos + i < seq.len() && element.allowed[seq[pos + i] as usize];
+            next.iter_mut().for_each(|x| *x = false);
+            for &offset in &reachable {
+                // Extend by `min..=max` accepted letters
+                for count in 0..=element.max {
+                    let end = offset + count;
+                    if count >= element.min {
+                        next[end] = true;
+                    }
+                    if count == element.max || !accepts(end) {
+                        break;
+                    }
+                }
+            }
+            reachable = (0..=limit).filter(|&offset| next[offset]).collect();
+            if reachable.is_empty() {
+                return false;
+            }
+        }
+        !self.anchored_end || reachable.contains(&(seq.len() - pos))
+    }
+}
+
+/// Return all positions and strands at which a degenerate pattern (IUPAC or PROSITE) occurs in a genetic sequence
+///
+/// Transcription-factor binding sites and protein motifs are rarely conserved letter by letter.
+/// Instead, they are described by patterns allowing a set of letters at certain positions,
+/// such as the TATA box `TATAWAWR` written in IUPAC ambiguity codes,
+/// or the N-glycosylation site `N-{P}-[ST]-{P}` written in PROSITE notation.
+///
+/// For nucleotide patterns, the reverse complement strand can be searched as well.
+/// Matches on the reverse strand are reported by the leftmost position they cover on the forward strand,
+/// together with the strand they were found on. Positions are ordered, forward before reverse strand matches.
+///  
+/// # Arguments
+/// * `seq` - genetic sequence to search for the pattern
+/// * `pattern` - compiled pattern, see `Pattern::from_iupac` and `Pattern::from_prosite`
+/// * `both_strands` - also search the reverse complement of `seq` (nucleotide patterns only)
+///
+/// # Example
+/// ```
+/// use biogarden::processing::patterns::{find_pattern, Pattern};
+/// use biogarden::ds::sequence::{Sequence, Strand};
+///
+/// let promoter = Sequence::from("GCGTATAAAAGGCCTTTTATAC");
+/// let tata_box = Pattern::from_iupac("TATAWAWR").unwrap();
+/// assert_eq!(find_pattern(&promoter, &tata_box, false).unwrap(), [(3, Strand::Forward)]);
+/// assert_eq!(find_pattern(&promoter, &tata_box, true).unwrap(), [(3, Strand::Forward), (13, Strand::Reverse)]);
+///
+/// let protein = Sequence::from("AANASTNPTAA");
+/// let glycosylation = Pattern::from_prosite("N-{P}-[ST]-{P}").unwrap();
+/// assert_eq!(find_pattern(&protein, &glycosylation, false).unwrap(), [(2, Strand::Forward)]);
+///
+/// // Patterns have to match at least one letter
+/// assert!(Pattern::from_prosite("x(0,2)").is_err());
+/// ```
+pub fn find_pattern(seq: &Sequence, pattern: &Pattern, both_strands: bool) -> Result<Vec<(usize, Strand)>> {
+    let mut positions: Vec<(usize, Strand)> = pattern.find(seq).into_iter().map(|pos| (pos, Strand::Forward)).collect();
+    if both_strands {
+        positions.extend(pattern.reverse_complement()?.find(seq).into_iter().map(|pos| (pos, Strand::Reverse)));
+        positions.sort_by_key(|&(pos, strand)| (pos, strand == Strand::Reverse));
+    }
+    Ok(positions)
+}
+
 /// Return the longest shared non-contiguous motif of genes in two genetic strings.
 ///
 /// Complexity: O(mn)
@@ -234,6 +532,289 @@ pub fn shortest_common_supersequence(seq1: &Sequence, seq2: &Sequence) -> Sequen
     Sequence::from(superseq)
 }
 
+/// Position of a read within a superstring
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadPlacement {
+    /// Index of the read in the input
+    pub read: usize,
+    /// Position of the first letter of the read within the superstring
+    pub offset: usize,
+}
+
+/// Superstring of a set of reads, as computed by `shortest_common_superstring`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Superstring {
+    /// Sequence containing every read as a substring
+    pub sequence: Sequence,
+    /// Placement of every read, ordered by offset
+    pub layout: Vec<ReadPlacement>,
+}
+
+/// Glue a set of reads into a short superstring, by greedily merging the reads with maximum overlap
+///
+/// Complexity: O(n^2 + k log k), with `n` - total length of the reads, `k` - number of overlapping read pairs
+///
+/// The classic model of genome assembly: find the shortest string containing every read,
+/// which is NP-hard in general. The greedy algorithm repeatedly joins the two reads with the longest
+/// suffix-prefix overlap, and is guaranteed to yield a superstring at most 4 times longer than the optimum.
+/// If every pair of consecutive reads overlaps by more than half of their length, the genome is recovered uniquely.
+/// Overlaps are located by walking the suffixes of each read along a trie of all reads,
+/// reads contained within other reads are placed within their containing read.
+/// Reads without any overlap are concatenated in order of their index.
+///
+/// # Arguments
+/// * `reads` - reads to be glued together
+///
+/// # Example
+/// ```
+/// use biogarden::processing::patterns::shortest_common_superstring;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// let mut reads = Tile::new();
+/// reads.push(Sequence::from("ATTAGACCTG"));
+/// reads.push(Sequence::from("CCTGCCGGAA"));
+/// reads.push(Sequence::from("AGACCTGCCG"));
+/// reads.push(Sequence::from("GCCGGAATAC"));
+/// reads.push(Sequence::from("CTGCCG"));
+///
+/// let superstring = shortest_common_superstring(&reads);
+/// assert_eq!(superstring.sequence, Sequence::from("ATTAGACCTGCCGGAATAC"));
+///
+/// // Reads ordered by their offset, the fifth read is contained in the second
+/// let layout: Vec<(usize, usize)> = superstring.layout.iter().map(|p| (p.read, p.offset)).collect();
+/// assert_eq!(layout, vec![(0, 0), (2, 3), (1, 6), (4, 7), (3, 9)]);
+/// ```
+pub fn shortest_common_superstring(reads: &Tile) -> Superstring {
+    let index = ReadOverlaps::new(reads);
+
+    // Consider overlaps in decreasing order of length
+    let mut overlaps = index.overlaps.clone();
+    overlaps.sort_by_key(|&(a, b, len)| (std::cmp::Reverse(len), a, b));
+
+    // Join reads, unless a read already has a successor or predecessor, or the join closes a cycle
+    let mut next = vec![None; reads.len()];
+    let mut has_prev = vec![false; reads.len()];
+    let mut chain: Vec<usize> = (0..reads.len()).collect();
+    for (a, b, len) in overlaps {
+        let (chain_a, chain_b) = (find_chain(&mut chain, a), find_chain(&mut chain, b));
+        if next[a].is_none() && !has_prev[b] && chain_a != chain_b {
+            next[a] = Some((b, len));
+            has_prev[b] = true;
+            chain[chain_b] = chain_a;
+        }
+    }
+
+    // Collect the chains of reads, ordered by the index of their first read
+    let mut order = Vec::<(usize, usize)>::new();
+    for start in (0..reads.len()).filter(|&i| index.container[i].is_none() && !has_prev[i]) {
+        order.push((start, 0));
+        let mut read = start;
+        while let Some((succ, len)) = next[read] {
+            order.push((succ, len));
+            read = succ;
+        }
+    }
+    index.spell(reads, &order)
+}
+
+/// Maximum number of reads, not contained in other reads, accepted by `shortest_common_superstring_exact`
+pub const MAX_EXACT_SUPERSTRING_READS: usize = 16;
+
+/// Compute a shortest common superstring of a small set of reads
+///
+/// Complexity: O(2^m * m^2 + n^2), with `m` - number of reads not contained in other reads, `n` - total length of the reads
+///
+/// Solves the underlying maximum overlap path problem exactly by dynamic programming over subsets of reads
+/// (Held-Karp). Unlike `shortest_common_superstring`, the result is optimal also if the greedy choice of
+/// the longest overlap misleads, at exponential cost. Fails with `InvalidInputSize` for more than
+/// `MAX_EXACT_SUPERSTRING_READS` reads, not counting reads contained in other reads.
+///
+/// # Arguments
+/// * `reads` - reads to be glued together
+///
+/// # Example
+/// ```
+/// use biogarden::processing::patterns::{shortest_common_superstring, shortest_common_superstring_exact};
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// let mut reads = Tile::new();
+/// reads.push(Sequence::from("CATAT"));
+/// reads.push(Sequence::from("TATA"));
+/// reads.push(Sequence::from("ATATG"));
+///
+/// // Greedily joining CATAT and ATATG by their overlap of four letters leaves no overlap for TATA
+/// assert_eq!(shortest_common_superstring(&reads).sequence, Sequence::from("CATATGTATA"));
+///
+/// let superstring = shortest_common_superstring_exact(&reads).unwrap();
+/// assert_eq!(superstring.sequence, Sequence::from("CATATATG"));
+/// ```
+pub fn shortest_common_superstring_exact(reads: &Tile) -> Result<Superstring> {
+    let index = ReadOverlaps::new(reads);
+
+    let kept: Vec<usize> = (0..reads.len()).filter(|&i| index.container[i].is_none()).collect();
+    let m = kept.len();
+    if m > MAX_EXACT_SUPERSTRING_READS {
+        return Err(BioError::InvalidInputSize);
+    }
+
+    // Overlap lengths between the kept reads
+    let mut position = vec![usize::MAX; reads.len()];
+    for (k, &i) in kept.iter().enumerate() {
+        position[i] = k;
+    }
+    let mut overlap = vec![vec![0; m]; m];
+    for &(a, b, len) in &index.overlaps {
+        overlap[position[a]][position[b]] = len;
+    }
+
+    // Maximum total overlap of a path visiting the reads of `mask`, ending at a given read
+    let mut best = vec![vec![None; m]; 1 << m];
+    let mut pred = vec![vec![usize::MAX; m]; 1 << m];
+    for k in 0..m {
+        best[1 << k][k] = Some(0);
+    }
+    for mask in 1..(1_usize << m) {
+        for last in (0..m).filter(|&k| mask & (1 << k) != 0) {
+            let Some(total) = best[mask][last] else {
+                continue;
+            };
+            for k in (0..m).filter(|&k| mask & (1 << k) == 0) {
+                let candidate = total + overlap[last][k];
+                let next = mask | (1 << k);
+                if best[next][k].is_none_or(|t| candidate > t) {
+                    best[next][k] = Some(candidate);
+                    pred[next][k] = last;
+                }
+            }
+        }
+    }
+
+    // Trace the optimal path back from its last read
+    let mut order = Vec::<(usize, usize)>::new();
+    if m > 0 {
+        let mut mask = (1 << m) - 1;
+        let mut last = (0..m).max_by_key(|&k| (best[mask][k], std::cmp::Reverse(k))).unwrap();
+        while mask != 0 {
+            let prev = pred[mask][last];
+            order.push((kept[last], if prev == usize::MAX { 0 } else { overlap[prev][last] }));
+            mask &= !(1 << last);
+            last = prev;
+        }
+        order.reverse();
+    }
+    Ok(index.spell(reads, &order))
+}
+
+// Maximal suffix-prefix overlaps and containments of reads, located by walking suffixes along a trie of the reads
+struct ReadOverlaps {
+    // Read containing a read, with the offset within the containing read
+    container: Vec<Option<(usize, usize)>>,
+    // Longest overlaps (a, b, length) of a suffix of `a` with a prefix of `b`, between reads not contained in others
+    overlaps: Vec<(usize, usize, usize)>,
+}
+
+impl ReadOverlaps {
+
+    fn new(reads: &Tile) -> ReadOverlaps {
+
+        // Trie of the reads, with the reads passing through and ending at every node
+        let mut children = vec![HashMap::<u8, usize>::new()];
+        let mut passing = vec![Vec::<usize>::new()];
+        let mut ending = vec![Vec::<usize>::new()];
+        for (i, read) in reads.into_iter().enumerate() {
+            let mut node = 0;
+            for &x in read {
+                node = match children[node].get(&x) {
+                    Some(&child) => child,
+                    None => {
+                        let child = children.len();
+                        children.push(HashMap::new());
+                        passing.push(vec![]);
+                        ending.push(vec![]);
+                        children[node].insert(x, child);
+                        child
+                    }
+                };
+                passing[node].push(i);
+            }
+            ending[node].push(i);
+        }
+
+        let mut container = vec![None; reads.len()];
+        let mut overlaps = Vec::<(usize, usize, usize)>::new();
+        for (a, read) in reads.into_iter().enumerate() {
+            let mut seen = HashSet::<usize>::new();
+            for offset in 0..read.len().max(1) {
+                // Walk the suffix starting at `offset`, reads ending on the way occur within `read`
+                let mut node = 0;
+                let mut depth = offset;
+                loop {
+                    for &b in &ending[node] {
+                        // Equal reads are contained in the one with the smallest index
+                        let contained = reads[b].len() < read.len() || b > a;
+                        if b != a && contained && container[b].is_none() {
+                            container[b] = Some((a, offset));
+                        }
+                    }
+                    match read.chain.get(depth).and_then(|x| children[node].get(x)) {
+                        Some(&child) => {
+                            node = child;
+                            depth += 1;
+                        }
+                        None => break,
+                    }
+                }
+                // Reads extending beyond the end of a suffix overlap with it, longest overlaps are found first
+                if depth == read.len() && offset > 0 {
+                    for &b in &passing[node] {
+                        if b != a && reads[b].len() > read.len() - offset && seen.insert(b) {
+                            overlaps.push((a, b, read.len() - offset));
+                        }
+                    }
+                }
+            }
+        }
+        overlaps.retain(|&(a, b, _)| container[a].is_none() && container[b].is_none());
+
+        ReadOverlaps { container, overlaps }
+    }
+
+    // Spell the superstring of reads joined in order, given by pairs of read and overlap with its predecessor
+    fn spell(&self, reads: &Tile, order: &[(usize, usize)]) -> Superstring {
+        let mut sequence = Sequence::new();
+        let mut offsets = vec![0; reads.len()];
+        for &(read, overlap) in order {
+            offsets[read] = sequence.len() - overlap;
+            sequence.chain.extend_from_slice(&reads[read][overlap..]);
+        }
+
+        // Place contained reads relative to the read containing them
+        let mut layout = Vec::<ReadPlacement>::new();
+        for read in 0..reads.len() {
+            let mut offset = 0;
+            let mut root = read;
+            while let Some((container, shift)) = self.container[root] {
+                offset += shift;
+                root = container;
+            }
+            layout.push(ReadPlacement { read, offset: offsets[root] + offset });
+        }
+        layout.sort_by_key(|p| (p.offset, p.read));
+        Superstring { sequence, layout }
+    }
+}
+
+fn find_chain(chain: &mut [usize], read: usize) -> usize {
+    let mut root = read;
+    while chain[root] != root {
+        root = chain[root];
+    }
+    chain[read] = root;
+    root
+}
+
 /// Return the longest common substring of multiple genetic sequences
 ///
 /// Complexity: O(mn), with `m` - number of sequences, `n` - length of longest sequence
@@ -241,6 +822,8 @@ pub fn shortest_common_supersequence(seq1: &Sequence, seq2: &Sequence) -> Sequen
 /// The motif shared by multiple organisms is often not known in advance.
 /// Therefore, it might be required to find the longest interval of shared genes.
 /// The implemented function performs the search using a suffix tree, that is built and traversed in `O(mn)`.
+/// For repeated queries, or substrings shared by only some of the sequences,
+/// see `ds::builders::generalized_suffix_tree::GeneralizedSuffixTree`.
 ///  
 /// # Arguments
 /// * `tile` - container holding `m` genetic sequences
@@ -337,6 +920,8 @@ pub fn longest_common_substring(tile: &Tile, alphabet: &HashSet::<u8>, bound: us
 /// When an enzyme disarms a virus gene, it cuts the virus DNA at spots denoted as restriction sites.
 /// This process is most effective, when the two target strands appear directly across from each other along the viral DNA.
 /// Something that occurs precisely, when the target is equal to its own reverse complement.
+/// Substrings are derived from the longest reverse palindrome around every center,
+/// see `InvertedRepeatFinder` for inverted repeats with a loop or mismatches.
 ///  
 /// # Arguments
 /// * `dna` - sequence to search for substrings that are equivalent to their reverse complement
@@ -356,33 +941,182 @@ pub fn longest_common_substring(tile: &Tile, alphabet: &HashSet::<u8>, bound: us
 /// assert_eq!(reverse_complement_substrings(&a, min_bound, max_bound), reverses);
 /// ```
 pub fn reverse_complement_substrings(dna: &Sequence, min_len: usize, max_len: usize) -> Vec<(usize, usize)> {
+    // Maximal palindromes around every center, i.e. inverted repeats without a loop
+    let finder = InvertedRepeatFinder::new()
+        .with_min_stem(std::cmp::max(min_len.div_ceil(2), 1))
+        .with_loop(0, 0);
     let mut palindromes: Vec<(usize, usize)> = vec![];
-    let complements = HashMap::from([(b'A', b'T'), (b'T', b'A'), (b'G', b'C'), (b'C', b'G')]);
-
-    // iterate over every offset within the initial string
-    for i in 0..dna.len() {
-        // iterate over possible lengths of palindromic substrings
-        for j in min_len..(max_len + 1) {
-            // break if potential substring cannot fit
-            if i + j > dna.len() {
-                break;
-            }
-            // check if substring with length `j` at offset `i`
-            // is a reverse palindrome
-            let mut is_palindrome = true;
-            for k in 0..j {
-                if dna.chain[i + k] != complements[&dna.chain[i + j - 1 - k]] {
-                    is_palindrome = false;
-                    break;
+    for repeat in finder.find(dna).unwrap() {
+        // Every shorter palindrome around the same center is a reverse palindrome as well
+        let center = repeat.spacer.start;
+        for half in min_len.div_ceil(2)..=std::cmp::min(repeat.stem, max_len / 2) {
+            palindromes.push((center - half, 2 * half));
+        }
+    }
+    palindromes.sort();
+    palindromes
+}
+
+/// Inverted repeat, i.e. two arms that are reverse complements of each other, separated by a loop
+///
+/// The arms of an inverted repeat can pair to form a stem-loop (hairpin), such as in transcription terminators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvertedRepeat {
+    /// Coordinates of the left (5') arm
+    pub left_arm: Range<usize>,
+    /// Coordinates of the loop (spacer) between the arms, empty for palindromes
+    pub spacer: Range<usize>,
+    /// Coordinates of the right (3') arm
+    pub right_arm: Range<usize>,
+    /// Length of each arm, i.e. number of base pairs in the stem
+    pub stem: usize,
+    /// Number of pairs within the stem that are not complementary
+    pub mismatches: usize,
+}
+
+/// Find inverted repeats with a loop (stem-loop candidates), allowing for mismatches in the stem
+///
+/// Complexity: O(nLS), with `n` - length of the sequence, `L` - number of loop lengths, `S` - length of the extended stems
+///
+/// For every loop position and length, the stem is extended outwards from the loop by comparing the letters
+/// on both sides directly, skipping over mismatches until `max_mismatches` is exceeded. Stems start and end with a base pair.
+/// Only the longest stem around every loop is reported, and loops are as short as possible, i.e.
+/// stem-loops obtained by moving base pairs from the stem into the loop are not reported.
+/// Letters are compared case-insensitively, `U` pairs like `T`, other letters (e.g. `N`) never pair.
+/// By default, exact stems of at least 6 base pairs with loops of 3 to 20 letters are reported.
+///
+/// # Example
+/// ```
+/// use biogarden::processing::patterns::InvertedRepeatFinder;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// // Stem `TAGCCGCC`, loop `TTTT`
+/// let seq = Sequence::from("ATAGCCGCCTTTTGGCGGCTA");
+/// let repeats = InvertedRepeatFinder::new().find(&seq).unwrap();
+/// assert_eq!(repeats.len(), 1);
+/// assert_eq!(repeats[0].left_arm, 1..9);
+/// assert_eq!(repeats[0].spacer, 9..13);
+/// assert_eq!(repeats[0].right_arm, 13..21);
+/// assert_eq!(repeats[0].stem, 8);
+///
+/// // Mismatch `G`-`A` within the stem
+/// let seq = Sequence::from("CGCAGTCGCCTTTTGGCAACTGCGA");
+/// let repeats = InvertedRepeatFinder::new().with_max_mismatches(1).find(&seq).unwrap();
+/// assert_eq!(repeats[0].left_arm, 0..10);
+/// assert_eq!(repeats[0].spacer, 10..14);
+/// assert_eq!(repeats[0].mismatches, 1);
+/// ```
+pub struct InvertedRepeatFinder {
+    min_stem: usize,
+    min_loop: usize,
+    max_loop: usize,
+    max_mismatches: usize,
+}
+
+impl InvertedRepeatFinder {
+
+    pub fn new() -> InvertedRepeatFinder {
+        InvertedRepeatFinder { min_stem: 6, min_loop: 3, max_loop: 20, max_mismatches: 0 }
+    }
+
+    /// Report inverted repeats with at least `min_stem` base pairs
+    pub fn with_min_stem(mut self, min_stem: usize) -> Self {
+        self.min_stem = min_stem;
+        self
+    }
+
+    /// Search for loops of `min_loop` to `max_loop` letters, a loop of 0 letters yields reverse palindromes
+    pub fn with_loop(mut self, min_loop: usize, max_loop: usize) -> Self {
+        self.min_loop = min_loop;
+        self.max_loop = max_loop;
+        self
+    }
+
+    /// Number of pairs within the stem that may not be complementary
+    pub fn with_max_mismatches(mut self, max_mismatches: usize) -> Self {
+        self.max_mismatches = max_mismatches;
+        self
+    }
+
+    /// Find all inverted repeats within a sequence, ordered by the start of the left arm and the loop
+    ///
+    /// Fails with `InvalidArgumentRange` for a stem of 0 base pairs or an empty range of loop lengths.
+    pub fn find(&self, seq: &Sequence) -> Result<Vec<InvertedRepeat>> {
+        if self.min_stem == 0 || self.min_loop > self.max_loop {
+            return Err(BioError::InvalidArgumentRange);
+        }
+
+        // Normalize the letters, such that letters that do not pair are never complementary
+        let forward: Vec<u8> = seq.chain.iter()
+            .map(|c| match c.to_ascii_uppercase() {
+                b'U' => b'T',
+                c @ (b'A' | b'C' | b'G' | b'T') => c,
+                _ => b'N',
+            })
+            .collect();
+        let n = forward.len();
+
+        let mut repeats = vec![];
+        for len in self.min_loop..=self.max_loop {
+            for start in 1..n.saturating_sub(len) {
+                let end = start + len;
+                // A loop closed by a base pair belongs to a longer stem around a shorter loop
+                if len >= self.min_loop + 2 && complement_base(forward[start]) == forward[end - 1] {
+                    continue;
+                }
+                // Number of base pairs formed outwards from the `t`-th letter left and right of the loop
+                let pairs = |t: usize| (t..start)
+                    .take_while(|&u| end + u < n && complement_base(forward[start - 1 - u]) == forward[end + u])
+                    .count();
+                let mut extent = pairs(0);
+                if extent == 0 {
+                    continue;
+                }
+
+                // Jump over mismatches, the stem ends with the last base pair
+                let (mut stem, mut mismatches, mut used) = (extent, 0, 0);
+                while used < self.max_mismatches && extent < start && end + extent < n {
+                    used += 1;
+                    extent += 1;
+                    let run = pairs(extent);
+                    extent += run;
+                    if run > 0 {
+                        stem = extent;
+                        mismatches = used;
+                    }
+                }
+
+                if stem >= self.min_stem {
+                    repeats.push(InvertedRepeat {
+                        left_arm: start - stem..start,
+                        spacer: start..end,
+                        right_arm: end..end + stem,
+                        stem,
+                        mismatches,
+                    });
                 }
-            }
-            // append (offset, length) into result set
-            if is_palindrome {
-                palindromes.push((i, j));
             }
         }
+        repeats.sort_by_key(|repeat| (repeat.left_arm.start, repeat.spacer.len()));
+        Ok(repeats)
+    }
+}
+
+impl Default for InvertedRepeatFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Watson-Crick complement of a normalized base, other letters are mapped to a letter that never pairs
+fn complement_base(base: u8) -> u8 {
+    match base {
+        b'A' => b'T',
+        b'T' => b'A',
+        b'C' => b'G',
+        b'G' => b'C',
+        _ => b'X',
     }
-    palindromes
 }
 
 /// Get positions where bases from one genetic string occur in another genome as a subsequence
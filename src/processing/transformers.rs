@@ -2,12 +2,12 @@ use allwords::Alphabet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-use crate::ds::builders::trie::{Trie, TrieNode};
-use crate::ds::graph::Graph;
+use crate::ds::algo::traversal::DfsPreorder;
+use crate::ds::builders::trie::Trie;
 use crate::ds::sequence::Sequence;
 use crate::ds::tile::Tile;
 use crate::error::{BioError, Result};
-use crate::processing::constants;
+use crate::processing::genetic_code::GeneticCode;
 use crate::processing::patterns::find_motif;
 use crate::analysis::seq::hamming_distance;
 
@@ -38,6 +38,7 @@ pub fn transcribe_dna(dna: Sequence) -> Sequence {
 ///
 /// In the double helix, adenine (A) always bonds with Thymine (T), and cytosine (C) always bonds with guanine (G).
 /// To generate the complementary strand of the primary, a strand must be reversed and bases must be swapped: A-T and G-C.
+/// Only uppercase `ACGT` is complemented, see `reverse_complement` for RNA and ambiguous nucleotides.
 ///
 /// # Arguments
 /// * `dna` - DNA string to transcribe into RNA
@@ -65,6 +66,71 @@ pub fn complement_dna(dna: Sequence) -> Sequence {
         .collect()
 }
 
+/// Reverse complement of a DNA sequence, supporting the full IUPAC nucleotide code
+///
+/// In addition to `A-T` and `G-C`, ambiguity codes are swapped with their complementary sets:
+/// `R-Y`, `K-M`, `B-V`, `D-H`, while `S`, `W`, `N` and gaps (`-`, `.`) are their own complement.
+/// `A` is complemented to `T`, use `reverse_complement_rna` to complement RNA sequences.
+/// Case is preserved, keeping soft-masked (lowercase) regions masked. Unknown letters are left unchanged.
+///
+/// # Arguments
+/// * `seq` - DNA sequence
+///
+/// # Example
+/// ```
+/// use biogarden::processing::transformers::reverse_complement;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// assert_eq!(reverse_complement(&Sequence::from("ACGTRYKMBDHVN-acgt")), Sequence::from("acgt-NBDHVKMRYACGT"));
+/// ```
+pub fn reverse_complement(seq: &Sequence) -> Sequence {
+    reverse_complement_nucleotides(seq, b'T')
+}
+
+/// Reverse complement of an RNA sequence, supporting the full IUPAC nucleotide code
+///
+/// Same as `reverse_complement`, except that `A` is complemented to `U`.
+///
+/// # Arguments
+/// * `seq` - RNA sequence
+///
+/// # Example
+/// ```
+/// use biogarden::processing::transformers::reverse_complement_rna;
+/// use biogarden::ds::sequence::Sequence;
+///
+/// assert_eq!(reverse_complement_rna(&Sequence::from("GAUUACA")), Sequence::from("UGUAAUC"));
+/// assert_eq!(reverse_complement_rna(&Sequence::from("GCCA")), Sequence::from("UGGC"));
+/// ```
+pub fn reverse_complement_rna(seq: &Sequence) -> Sequence {
+    reverse_complement_nucleotides(seq, b'U')
+}
+
+// Reverse complement with `A` complemented to `a_complement`, i.e. `T` for DNA and `U` for RNA
+fn reverse_complement_nucleotides(seq: &Sequence, a_complement: u8) -> Sequence {
+    seq.into_iter()
+        .rev()
+        .map(|&x| {
+            let complement = match x.to_ascii_uppercase() {
+                b'A' => a_complement,
+                b'T' | b'U' => b'A',
+                b'G' => b'C',
+                b'C' => b'G',
+                b'R' => b'Y',
+                b'Y' => b'R',
+                b'K' => b'M',
+                b'M' => b'K',
+                b'B' => b'V',
+                b'V' => b'B',
+                b'D' => b'H',
+                b'H' => b'D',
+                _ => return x,
+            };
+            if x.is_ascii_lowercase() { complement.to_ascii_lowercase() } else { complement }
+        })
+        .collect()
+}
+
 /// Translate an RNA sequence into proteins
 ///
 /// One of the central dogma in molecular biology is that DNA is transcribed into RNA, which in turn is translated into Protein.
@@ -90,6 +156,36 @@ pub fn complement_dna(dna: Sequence) -> Sequence {
 /// assert_eq!(translate_rna(rna, Some(2)), result);
 /// ```
 pub fn translate_rna(rna: Sequence, limit: Option<usize>) -> Tile {
+    translate_rna_with_code(rna, limit, &GeneticCode::standard(), false)
+}
+
+/// Translate an RNA sequence into proteins using the given genetic code
+///
+/// Behaves as `translate_rna`, but codons are decoded and translation is terminated according to the given table.
+/// By default only `AUG` initiates translation, with `alternative_starts` enabled any start codon of the table does.
+/// Start codons are always translated to methionine, regardless of the amino acid they encode elsewhere.
+///
+/// # Arguments
+/// * `rna` - RNA sequence to translate into proteins
+/// * `limit` - maximum number of protein to decode (many protein possible in one RNA)
+/// * `code` - genetic code, e.g. `GeneticCode::new(2)` for vertebrate mitochondria
+/// * `alternative_starts` - initiate translation at alternative start codons of the table
+///
+/// # Example
+/// ```
+/// use biogarden::processing::transformers::translate_rna_with_code;
+/// use biogarden::processing::genetic_code::GeneticCode;
+/// use biogarden::ds::sequence::Sequence;
+/// use biogarden::ds::tile::Tile;
+///
+/// let rna = Sequence::from("AUAGCCUGAAUGAGA");
+/// let mito = GeneticCode::new(2).unwrap();
+///
+/// let mut result = Tile::new();
+/// result.push(Sequence::from("MAWM"));
+/// assert_eq!(translate_rna_with_code(rna, Some(1), &mito, true), result);
+/// ```
+pub fn translate_rna_with_code(rna: Sequence, limit: Option<usize>, code: &GeneticCode, alternative_starts: bool) -> Tile {
     // TODO: Refactor into iterator/generator
     let mut proteins = Tile::new();
 
@@ -107,8 +203,8 @@ pub fn translate_rna(rna: Sequence, limit: Option<usize>) -> Tile {
             // Take 3 characters from strand, that denote codon
             let chunk: String = z.by_ref().take(3).collect();
             // Check for start codon
-            if chunk == "AUG" {
-                amino_acid.push_str(constants::CODON_TABLE.get(&chunk as &str).unwrap());
+            if chunk == "AUG" || (alternative_starts && code.is_start(chunk.as_bytes())) {
+                amino_acid.push('M');
                 break;
             }
         }
@@ -118,14 +214,14 @@ pub fn translate_rna(rna: Sequence, limit: Option<usize>) -> Tile {
         while zi.peek().is_some() {
             // Take 3 characters from strand, that denote codon
             let chunk: String = zi.by_ref().take(3).collect();
-            match constants::CODON_TABLE.get(&chunk as &str) {
+            match code.translate_codon(chunk.as_bytes()) {
                 Some(value) => {
                     // If stop codon reached, store current protein strand and proceed
-                    if value == &"Stop" {
+                    if value == b'*' {
                         proteins.push(Sequence::from(amino_acid.clone()));
                         break;
                     } else {
-                        amino_acid.push_str(value);
+                        amino_acid.push(value as char);
                     }
                 }
                 None => {
@@ -149,6 +245,7 @@ pub fn translate_rna(rna: Sequence, limit: Option<usize>) -> Tile {
 ///
 /// DNA can be transcribed into RNA, which in turn might be translated to protein.
 /// As a result, one might consider the direct transcription of DNA into protein.
+/// See `processing::orf::OrfFinder` for ORFs annotated with their coordinates, strand and frame.
 ///
 /// # Arguments
 /// * `dna` - dna sequence to decode into proteins
@@ -350,7 +447,7 @@ pub fn correct_read_errors(
         // Insert read if not present already and increment count
         *read_counter.entry(read.clone()).or_insert(0) += 1;
         // Handle complement case
-        let compl = complement_dna(read.clone());
+        let compl = reverse_complement(read);
         if read_counter.contains_key(&compl) {
             *read_counter.get_mut(&compl).ok_or(BioError::ItemNotFound)? += 1;
             *read_counter.get_mut(read).ok_or(BioError::ItemNotFound)? += 1;
@@ -379,7 +476,7 @@ pub fn correct_read_errors(
                 break;
             }
             // H(complement(x)) <= hamming_distance_margin
-            let complement = complement_dna(cr.clone());
+            let complement = reverse_complement(cr);
             if hamming_distance(fr, &complement)? == hd_margin {
                 corrections.push((fr.clone(), complement));
                 break;
@@ -430,29 +527,14 @@ pub fn sort_lexicographically(sequences: &Tile, alphabet: &[u8]) -> Result<Tile>
     // Build Trie data structure on which search is performed
     let mut trie_builder = Trie::new(alphabet);
     let trie = trie_builder.build(sequences)?;
-    // Define recursive function that is used to perform a DFS on the Trie
-    fn walk_trie_rec(trie: &Graph<TrieNode, u8>, node_id: u64, sorted: &mut Tile) {
-        // Leaf node found -> terminate search path
-        if trie.out_neighbors(node_id).count() == 0 {
-            return;
-        }
-        // Iterate over children and recurse
-        let node = trie.get_node(&node_id);
-        for &c in node.data.children.iter() {
-            if c != -1 {
-                if trie.get_node(&(c as u64)).data.ending == true {
-                    let substrings = &trie.get_node(&(c as u64)).data.substring;
-                    substrings
-                        .iter()
-                        .for_each(|s| sorted.push(Sequence::from(s)));
-                }
-                walk_trie_rec(trie, c as u64, sorted);
-            }
-        }
-    }
-    // Sort
-    let mut sorted = Tile::new();
+    // Depth-first search on the Trie, following the children in alphabetic order
+    let rank: HashMap<u8, usize> = alphabet.iter().enumerate().map(|(i, &c)| (c, i)).collect();
     let root = trie.get_root().ok_or(BioError::ItemNotFound)?;
-    walk_trie_rec(trie, root, &mut sorted);
+    let walk = DfsPreorder::new(trie, root)?.with_order(|edge| edge.data.map(|c| rank[&c]));
+    // Words ending at a node precede all words continuing below it
+    let mut sorted = Tile::new();
+    for node in walk {
+        trie.get_node(&node).data.substring.iter().for_each(|s| sorted.push(Sequence::from(s)));
+    }
     Ok(sorted)
 }
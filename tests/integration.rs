@@ -4,6 +4,7 @@ use biogarden::analysis;
 use biogarden::ds::sequence::Sequence;
 use biogarden::ds::tile::Tile;
 use biogarden::io::fasta::*;
+use biogarden::io::fastq::FastqRead;
 use biogarden::processing;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -148,6 +149,19 @@ mod integration {
         assert_eq!(scss, result);
     }
 
+    #[test]
+    fn shortest_common_superstring() {
+        let input = read_sequences("input/shortest_common_superstring.fasta");
+        let result = read_sequence("output/shortest_common_superstring.fasta");
+        let superstring = processing::patterns::shortest_common_superstring(&input);
+        assert_eq!(superstring.sequence, result);
+        assert_eq!(superstring.layout.len(), input.len());
+        for placement in &superstring.layout {
+            let read = &input[placement.read];
+            assert_eq!(superstring.sequence[placement.offset..placement.offset + read.len()], read.chain[..]);
+        }
+    }
+
     #[test]
     fn longest_common_substring() {
         let input = read_sequences("input/longest_common_substring.fasta");
@@ -171,6 +185,20 @@ mod integration {
         assert_eq!(processing::transformers::complement_dna(input), complement);
     }
 
+    #[test]
+    fn reverse_complement() {
+        let input = read_sequence("input/complement_dna.fasta");
+        let complement = read_sequence("output/complement_dna.fasta");
+        assert_eq!(processing::transformers::reverse_complement(&input), complement);
+        // Complement of RNA and soft-masked DNA
+        let rna = processing::transformers::transcribe_dna(input.clone());
+        let rna_complement = processing::transformers::transcribe_dna(complement.clone());
+        assert_eq!(processing::transformers::reverse_complement_rna(&rna), rna_complement);
+        let masked = Sequence::from(String::from(input).to_lowercase());
+        let masked_complement = Sequence::from(String::from(complement).to_lowercase());
+        assert_eq!(processing::transformers::reverse_complement(&masked), masked_complement);
+    }
+
     #[test]
     fn translate_rna() {
         let input = read_sequence("input/translate_rna.fasta");
@@ -187,6 +215,32 @@ mod integration {
         let orfs = read_sequences("output/orf.fasta");
         assert_eq!(processing::transformers::open_reading_frames(&input), orfs);
     }
+
+    #[test]
+    fn orf_finder() {
+        let input = read_sequence("input/orf.fasta");
+        let expected: HashSet<Sequence> = read_sequences("output/orf.fasta").into_iter().collect();
+        let orfs = processing::orf::OrfFinder::new()
+            .with_nested(processing::orf::NestedOrfs::All)
+            .find(&input);
+        assert!(orfs.iter().all(|orf| orf.is_complete()));
+        let proteins: HashSet<Sequence> = orfs.iter().map(|orf| orf.protein.clone()).collect();
+        assert_eq!(proteins, expected);
+
+        // Soft-masked DNA and RNA yield the same ORFs on both strands
+        let masked = Sequence::from(input.chain.to_ascii_lowercase());
+        let rna = Sequence::from(input.chain.iter().map(|&c| if c == b'T' { b'U' } else { c }).collect::<Vec<u8>>());
+        for seq in [masked, rna] {
+            let found = processing::orf::OrfFinder::new()
+                .with_nested(processing::orf::NestedOrfs::All)
+                .find(&seq);
+            assert_eq!(found, orfs);
+            let frames = processing::orf::six_frame_translation(&seq, &processing::genetic_code::GeneticCode::standard());
+            let expected_frames = processing::orf::six_frame_translation(&input, &processing::genetic_code::GeneticCode::standard());
+            assert!(frames.iter().zip(&expected_frames).all(|(a, b)| a.protein == b.protein));
+        }
+    }
+
     #[test]
     fn rna_splice() {
         let mut seq = read_sequence("input/rna_splice_seq.fasta");
@@ -311,4 +365,928 @@ mod integration {
         assert_eq!(s2_aligned, outputs[1]);
     }
 
+    #[test]
+    fn read_mapping() {
+        let reference = read_sequences("input/read_mapping.fasta");
+        let mut reads = Tile::new();
+        biogarden::io::fastq::Reader::from_file("./tests/data/input/read_mapping.fastq")
+            .unwrap()
+            .read_all(&mut reads)
+            .unwrap();
+        let mut mapper = alignment::mapper::ReadMapper::new(&reference, 12).unwrap();
+
+        let mappings = mapper.map_tile(&reads).unwrap();
+        let result: Vec<Option<String>> = mappings
+            .into_iter()
+            .map(|m| m.map(|m| format!("{} {} {} {:?} {}",
+                m.read_id.unwrap(), m.reference_id.unwrap(), m.position, m.strand, m.cigar)))
+            .collect();
+
+        assert_eq!(result, vec![
+            Some("read1 chr1 100 Forward 80M".to_string()),
+            Some("read2 chr1 250 Forward 80M".to_string()),
+            Some("read3 chr2 50 Reverse 80M".to_string()),
+            Some("read4 chr1 400 Forward 30M3D47M".to_string()),
+            None,
+        ]);
+    }
+
+    #[test]
+    fn fastq_quality_length() {
+        let mut reads = Tile::new();
+        let fastq = b"@r1\nACGT\nAC\n+\nIIIIII\n";
+        biogarden::io::fastq::Reader::new(&fastq[..]).read_all(&mut reads).unwrap();
+        assert_eq!(reads[0], Sequence::from("ACGTAC"));
+
+        // Quality string longer than the sequence
+        let fastq = b"@r1\nACGT\n+\nIIIIII\n";
+        let err = biogarden::io::fastq::Reader::new(&fastq[..]).read_all(&mut reads).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn de_bruijn_assembly() {
+        let reads = read_sequences("input/assembly_reads.fasta");
+        let genome = read_sequence("output/assembly_genome.fasta");
+
+        let contigs = biogarden::ds::algo::assembly::Assembler::new(31).unwrap().assemble(&reads);
+        assert_eq!(contigs.len(), 1);
+        assert!(contigs[0] == genome || contigs[0] == processing::transformers::reverse_complement(&genome));
+
+        let stats = analysis::stat::assembly_statistics(&contigs);
+        assert_eq!((stats.contigs, stats.n50, stats.n90), (1, 1500, 1500));
+    }
+
+    #[test]
+    fn gfa_round_trip() {
+        let reads = read_sequences("input/assembly_reads.fasta");
+        let graph = biogarden::ds::builders::debruijn::DeBruijnBuilder::new().with_k(31).with_counts(true).build(&reads);
+
+        let path = std::env::temp_dir().join("biogarden_gfa_round_trip.gfa");
+        let mut writer = biogarden::io::gfa::Writer::to_file(&path).unwrap();
+        writer.write_graph(&graph, |_| Some(29)).unwrap();
+        writer.flush().unwrap();
+
+        let gfa = biogarden::io::gfa::Reader::from_file(&path).unwrap().read_graph().unwrap();
+        assert_eq!(gfa.graph.node_count(), graph.node_count());
+        assert_eq!(gfa.graph.edge_count(), graph.edge_count());
+        let segments: HashSet<Sequence> = gfa.graph.nodes()
+            .map(|id| Sequence::from(gfa.graph.get_node(id).data.chain.clone()))
+            .collect();
+        assert_eq!(segments, graph.nodes().map(|id| graph.get_node(id).data.clone()).collect());
+        assert!(gfa.graph.edges().all(|e| gfa.graph.get_edge(e).data.unwrap().overlap == Some(29)));
+    }
+
+    #[test]
+    fn gfa_orientations() {
+        use biogarden::ds::sequence::Strand;
+        use biogarden::io::gfa::{Gfa, GfaLink, Reader, Writer};
+
+        let gfa = Reader::from_file("./tests/data/input/graph.gfa")
+            .unwrap()
+            .read_graph()
+            .unwrap();
+        // Links between segments in opposite orientation
+        assert_eq!((gfa.graph.node_count(), gfa.graph.edge_count()), (3, 2));
+        let links: Vec<GfaLink> = gfa.graph.edges().map(|e| gfa.graph.get_edge(e).data.unwrap()).collect();
+        assert_eq!(links[0], GfaLink { from_strand: Strand::Forward, to_strand: Strand::Reverse, overlap: Some(2) });
+        assert_eq!(links[1], GfaLink { from_strand: Strand::Reverse, to_strand: Strand::Forward, overlap: None });
+        let scaffold = &gfa.paths[0];
+        assert_eq!(scaffold.name, "scaffold");
+        let steps: Vec<(String, Strand)> = scaffold.nodes.iter()
+            .map(|(id, strand)| (gfa.graph.get_node(id).data.id.clone().unwrap(), *strand))
+            .collect();
+        assert_eq!(steps, vec![
+            ("ctg1".to_string(), Strand::Forward),
+            ("ctg2".to_string(), Strand::Reverse),
+            ("ctg3".to_string(), Strand::Forward),
+        ]);
+        assert_eq!(gfa.graph.get_node(&scaffold.nodes[1].0).data, Sequence::from("GGATCCAA"));
+        assert!(gfa.graph.get_node(&scaffold.nodes[2].0).data.is_empty());
+
+        // Write the graph and read it back
+        let path = std::env::temp_dir().join("biogarden_gfa_orientations.gfa");
+        let mut writer = Writer::to_file(&path).unwrap();
+        writer.write_gfa(&gfa).unwrap();
+        writer.flush().unwrap();
+        // Segment lengths, links and path overlaps are preserved, only the comment is dropped
+        let written = std::fs::read_to_string(&path).unwrap();
+        let original = std::fs::read_to_string("./tests/data/input/graph.gfa").unwrap();
+        let records: Vec<&str> = original.lines().filter(|line| !line.starts_with('#')).collect();
+        assert_eq!(written.lines().collect::<Vec<&str>>(), records);
+        assert_eq!(gfa.segment_length(scaffold.nodes[2].0), 100);
+        assert_eq!(gfa.segment_length(scaffold.nodes[0].0), 8);
+        assert_eq!(scaffold.overlaps, vec![Some(2), None]);
+
+        let read_back: Gfa = Reader::from_file(&path).unwrap().read_graph().unwrap();
+        let describe = |gfa: &Gfa| {
+            let edges: Vec<(Sequence, Sequence, GfaLink)> = gfa.graph.edges()
+                .map(|e| gfa.graph.get_edge(e))
+                .map(|e| (gfa.graph.get_node(&e.start).data.clone(), gfa.graph.get_node(&e.end).data.clone(), e.data.unwrap()))
+                .collect();
+            let paths: Vec<(String, Vec<(Sequence, Strand)>)> = gfa.paths.iter()
+                .map(|p| (p.name.clone(), p.nodes.iter().map(|(id, s)| (gfa.graph.get_node(id).data.clone(), *s)).collect()))
+                .collect();
+            (edges, paths)
+        };
+        assert_eq!(read_back.graph.node_count(), gfa.graph.node_count());
+        assert_eq!(describe(&read_back), describe(&gfa));
+
+        // Lengths of overlaps must be followed by an operation
+        for overlap in ["10", "5M3"] {
+            let gfa = format!("S\ta\tACGT\nS\tb\tCGTA\nL\ta\t+\tb\t+\t{}\n", overlap);
+            assert!(Reader::new(gfa.as_bytes()).read_graph().is_err());
+        }
+
+        // Segment names falling back to node ids must not clash with sequence ids
+        let mut graph = biogarden::ds::graph::Graph::<Sequence, usize>::new(biogarden::ds::graph::GraphProperties { directed: true });
+        let mut named = Sequence::from("ACGT");
+        named.id = Some("0".to_string());
+        graph.add_node(Sequence::from("GATTACA"));
+        graph.add_node(named);
+        let err = Writer::new(Vec::new()).write_graph(&graph, |_| None).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn string_graph() {
+        let genome = read_sequence("output/assembly_genome.fasta");
+
+        // Every third read, including the reads at both ends of the genome, suffices for a layout
+        let all_reads = read_sequences("input/assembly_reads.fasta");
+        let mut reads = Tile::new();
+        for (i, read) in (&all_reads).into_iter().enumerate() {
+            if i % 3 == 0 || i + 1 == all_reads.len() {
+                reads.push(read.clone());
+            }
+        }
+
+        let graph = biogarden::ds::builders::string_graph::StringGraphBuilder::new()
+            .with_min_overlap(50)
+            .with_both_strands(true)
+            .build(&reads)
+            .unwrap();
+
+        // Transitive reduction leaves a single path for each strand of the genome
+        let sources: Vec<u64> = graph.nodes().filter(|&id| graph.get_node(id).incoming().is_empty()).cloned().collect();
+        assert_eq!(sources.len(), 2);
+        assert!(graph.nodes().all(|id| graph.get_node(id).outgoing().len() <= 1));
+        assert_eq!(graph.edge_count(), graph.node_count() - 2);
+
+        // Layout of the reads along the path spans the genome
+        let mut node = sources[0];
+        let mut len = graph.get_node(&node).data.len();
+        while let Some(edge) = graph.get_node(&node).outgoing().first() {
+            let edge = graph.get_edge(edge);
+            node = edge.end;
+            len += graph.get_node(&node).data.len() - edge.data.unwrap();
+        }
+        assert!(len.abs_diff(genome.len()) <= 5);
+    }
+
+    #[test]
+    fn shortest_paths() {
+        use biogarden::ds::graph::{Graph, GraphProperties};
+
+        let build = |n: usize, edges: &[(usize, usize, i32)]| -> (Graph<usize, i32>, Vec<u64>) {
+            let mut graph = Graph::<usize, i32>::new(GraphProperties{directed: true});
+            let nodes: Vec<u64> = (1..=n).map(|i| graph.add_node(i)).collect();
+            for &(a, b, w) in edges {
+                graph.add_edge(&nodes[a - 1], &nodes[b - 1], Some(w)).unwrap();
+            }
+            (graph, nodes)
+        };
+
+        // Dijkstra, unreachable nodes at distance -1
+        let (graph, nodes) = build(6, &[(3, 4, 4), (1, 2, 4), (1, 3, 2), (2, 3, 3), (6, 3, 2),
+                                        (3, 5, 5), (5, 4, 1), (3, 2, 1), (2, 4, 2), (2, 5, 3)]);
+        let paths = biogarden::ds::algo::paths::dijkstra(&graph, nodes[0], |e| e.data.unwrap()).unwrap();
+        let distances: Vec<i32> = nodes.iter().map(|id| paths.distance(id).unwrap_or(-1)).collect();
+        assert_eq!(distances, vec![0, 3, 2, 5, 6, -1]);
+
+        // Bellman-Ford with negative weights
+        let (graph, nodes) = build(9, &[(1, 2, 10), (3, 2, 1), (3, 4, 1), (4, 5, 3), (5, 6, -1), (7, 6, -1), (8, 7, 1),
+                                        (1, 8, 8), (7, 2, -4), (2, 6, 2), (6, 3, -2), (9, 5, -1), (9, 4, 7)]);
+        let paths = biogarden::ds::algo::paths::bellman_ford(&graph, nodes[0], |e| e.data.unwrap()).unwrap();
+        let distances: Vec<Option<i32>> = nodes.iter().map(|id| paths.distance(id)).collect();
+        assert_eq!(distances, vec![Some(0), Some(5), Some(5), Some(6), Some(9), Some(7), Some(9), Some(8), None]);
+        let path: Vec<usize> = paths.path(&graph, nodes[4]).unwrap().iter().map(|&id| graph.node(id).unwrap().data).collect();
+        assert_eq!(path, vec![1, 8, 7, 2, 6, 3, 4, 5]);
+    }
+
+    #[test]
+    fn dag_paths() {
+        use biogarden::ds::algo::dag;
+        use biogarden::ds::graph::{Graph, GraphProperties};
+
+        let mut graph = Graph::<usize, u8>::new(GraphProperties{directed: true});
+        let nodes: Vec<u64> = (1..=4).map(|i| graph.add_node(i)).collect();
+        for (a, b) in [(1, 2), (3, 1), (3, 2), (4, 3), (4, 2)] {
+            graph.add_edge(&nodes[a - 1], &nodes[b - 1], None).unwrap();
+        }
+        let order: Vec<usize> = dag::topological_sort(&graph).unwrap().iter().map(|&id| graph.node(id).unwrap().data).collect();
+        assert_eq!(order, vec![4, 3, 1, 2]);
+
+        // Manhattan tourist problem, longest path through a grid of streets
+        let down = [[1, 0, 2, 4, 3], [4, 6, 5, 2, 1], [4, 4, 5, 2, 1], [5, 6, 8, 5, 3]];
+        let right = [[3, 2, 4, 0], [3, 2, 4, 2], [0, 7, 3, 3], [3, 3, 0, 2], [1, 3, 2, 2]];
+        let mut grid = Graph::<String, i32>::new(GraphProperties{directed: true});
+        let mut ids = vec![vec![0; 5]; 5];
+        for (i, row) in ids.iter_mut().enumerate() {
+            for (j, id) in row.iter_mut().enumerate() {
+                *id = grid.add_node(format!("{},{}", i, j));
+            }
+        }
+        for i in 0..5 {
+            for j in 0..5 {
+                if i < 4 {
+                    grid.add_edge(&ids[i][j], &ids[i + 1][j], Some(down[i][j])).unwrap();
+                }
+                if j < 4 {
+                    grid.add_edge(&ids[i][j], &ids[i][j + 1], Some(right[i][j])).unwrap();
+                }
+            }
+        }
+        let path = dag::dag_longest_path(&grid, Some(ids[0][0].into()), Some(ids[4][4].into()), |e| e.data.unwrap()).unwrap().unwrap();
+        assert_eq!(path.weight, 34);
+        assert_eq!(path.nodes.len(), 9);
+    }
+
+    #[test]
+    fn graph_components() {
+        use biogarden::ds::algo::components;
+
+        let reads = read_sequences("input/assembly_reads.fasta");
+        let graph = biogarden::ds::builders::debruijn::DeBruijnBuilder::new()
+            .with_k(31)
+            .with_counts(true)
+            .with_both_strands(true)
+            .build(&reads);
+
+        // Both strands of the genome form separate components of equal size
+        let weak = components::weakly_connected_components(&graph);
+        assert_eq!(weak.len(), 2);
+        assert_eq!(weak[0].len(), weak[1].len());
+
+        // Genome without repeats of length 30 yields an acyclic graph
+        let condensation = components::condensation(&graph);
+        assert_eq!(condensation.components.len(), graph.node_count());
+        assert_eq!(condensation.graph.edge_count(), graph.edge_count());
+    }
+
+    #[test]
+    fn eulerian_path() {
+        use biogarden::ds::algo::euler;
+        use biogarden::ds::graph::GraphErr;
+
+        let genome = read_sequence("output/assembly_genome.fasta");
+        let mut genomes = Tile::new();
+        genomes.push(genome.clone());
+
+        // Linear genome without repeats is spelled by the path from its first to its last (k-1)-mer
+        let graph = biogarden::ds::builders::debruijn::DeBruijnBuilder::new().with_k(31).build(&genomes);
+        let path = euler::eulerian_path(&graph).unwrap();
+        assert_eq!(path.edges.len(), graph.edge_count());
+        assert!(!path.is_circuit());
+        assert_eq!(euler::spell_path(&graph, &path.nodes), genome);
+
+        // Each strand forms a separate component
+        let graph = biogarden::ds::builders::debruijn::DeBruijnBuilder::new()
+            .with_k(31)
+            .with_both_strands(true)
+            .build(&genomes);
+        assert_eq!(euler::eulerian_path(&graph), Err(GraphErr::DisconnectedGraph));
+        let spelled: HashSet<Sequence> = euler::eulerian_paths(&graph)
+            .unwrap()
+            .iter()
+            .map(|path| euler::spell_path(&graph, &path.nodes))
+            .collect();
+        assert_eq!(spelled, HashSet::from([genome.clone(), processing::transformers::reverse_complement(&genome)]));
+    }
+
+    #[test]
+    fn graph_traversal() {
+        use biogarden::ds::algo::{euler, traversal};
+
+        let genome = read_sequence("output/assembly_genome.fasta");
+        let mut genomes = Tile::new();
+        genomes.push(genome);
+        let graph = biogarden::ds::builders::debruijn::DeBruijnBuilder::new().with_k(31).build(&genomes);
+        let path = euler::eulerian_path(&graph).unwrap();
+        let (first, last) = (path.nodes[0], *path.nodes.last().unwrap());
+
+        // Graph of a genome without repeats is a single path, traversed in the same order by all iterators
+        let bfs: Vec<u64> = traversal::Bfs::new(&graph, first).unwrap().collect();
+        let preorder: Vec<u64> = traversal::DfsPreorder::new(&graph, first).unwrap().collect();
+        let mut postorder: Vec<u64> = traversal::DfsPostorder::new(&graph, first).unwrap().collect();
+        postorder.reverse();
+        assert_eq!(bfs, path.nodes);
+        assert_eq!(preorder, path.nodes);
+        assert_eq!(postorder, path.nodes);
+
+        let mut reversed: Vec<u64> = traversal::Bfs::new(&graph, last).unwrap().reversed().collect();
+        reversed.reverse();
+        assert_eq!(reversed, path.nodes);
+
+        // Stop after the first 100 edges
+        let mut walked = 0;
+        let pruned = traversal::DfsPreorder::new(&graph, first).unwrap().with_filter(|_| {
+            walked += 1;
+            walked <= 100
+        });
+        assert_eq!(pruned.count(), 101);
+    }
+
+    #[test]
+    fn graph_export() {
+        use biogarden::io::graph::{GraphExporter, GraphFormat};
+
+        let reads = read_sequences("input/assembly_reads.fasta");
+        let graph = biogarden::ds::builders::debruijn::DeBruijnBuilder::new().with_k(31).with_counts(true).build(&reads);
+
+        // Colour edges by k-mer coverage
+        let export = |format: GraphFormat| -> String {
+            let mut out = Vec::new();
+            GraphExporter::new(&graph, format)
+                .with_graph_attribute("rankdir", "LR")
+                .with_node_label(|_| None)
+                .with_edge_attributes(|e| {
+                    let colour = if e.data.unwrap() > 1 { "black" } else { "red" };
+                    vec![(String::from("color"), String::from(colour))]
+                })
+                .write(&mut out)
+                .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let dot = export(GraphFormat::Dot);
+        assert_eq!(dot.matches(" -> ").count(), graph.edge_count());
+        assert!(dot.contains("color=\"red\""));
+
+        let graphml = export(GraphFormat::GraphMl);
+        assert_eq!(graphml.matches("<node ").count(), graph.node_count());
+        assert_eq!(graphml.matches("<edge ").count(), graph.edge_count());
+        assert!(graphml.contains("<key id=\"edge_color\" for=\"edge\" attr.name=\"color\" attr.type=\"string\"/>"));
+
+        let json = export(GraphFormat::Json);
+        assert_eq!(json.matches("\"source\": ").count(), graph.edge_count());
+        assert!(json.contains("\"graph\": {\"name\": \"G\", \"rankdir\": \"LR\"}"));
+
+        let path = std::env::temp_dir().join("biogarden_graph_export.dot");
+        graph.write_dot(path.to_str().unwrap()).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), graph.node_count() + graph.edge_count() + 2);
+    }
+
+    #[test]
+    fn generalized_suffix_tree() {
+        use biogarden::ds::builders::generalized_suffix_tree::GeneralizedSuffixTreeBuilder;
+
+        let input = read_sequences("input/longest_common_substring.fasta");
+        let result = read_sequence("output/longest_common_substring.fasta");
+        let gst = GeneralizedSuffixTreeBuilder::new().build(&input);
+
+        let lcs = gst.longest_common_substring(input.len()).unwrap();
+        assert_eq!(lcs.len(), result.len());
+        assert_eq!(gst.graph().get_node(&gst.locus(&lcs.chain).unwrap()).data.sequences, input.len());
+        assert!(gst.common_substrings(input.len(), 1).contains(&result));
+
+        // Occurrences agree with a naive search
+        let mut naive = Vec::<(usize, usize)>::new();
+        for (i, seq) in input.into_iter().enumerate() {
+            for (j, window) in seq.chain.windows(result.len()).enumerate() {
+                if window == &result.chain[..] {
+                    naive.push((i, j));
+                }
+            }
+        }
+        assert_eq!(gst.locate(&result.chain), naive);
+        assert_eq!(gst.count(&result.chain), naive.len());
+
+        // Longest common extension of the first two occurrences covers the shared substring
+        let ((s1, p1), (s2, p2)) = (naive[0], naive[1]);
+        assert!(gst.lce(s1, p1, s2, p2) >= result.len());
+        for repeat in gst.supermaximal_repeats(result.len()) {
+            assert!(repeat.occurrences.len() >= 2);
+        }
+    }
+
+    #[test]
+    fn tandem_repeats() {
+        use biogarden::analysis::repeats::TandemRepeatFinder;
+
+        // Insert an exact microsatellite and an imperfect minisatellite into a background sequence
+        let background = read_sequence("input/linguistic_complexity.fasta");
+        let unit = "GATTACAGGC";
+        let mut minisatellite = unit.repeat(5).into_bytes();
+        minisatellite[23] = b'T';
+        let mut chain = background.chain.clone();
+        let (micro, mini) = (chain.len() / 3, 2 * chain.len() / 3);
+        chain.splice(mini..mini, minisatellite);
+        chain.splice(micro..micro, "CA".repeat(15).into_bytes());
+        let seq = Sequence::from(chain);
+        let (micro, mini) = (micro, mini + 30);
+
+        let repeats = TandemRepeatFinder::new().with_min_length(20).find(&seq).unwrap();
+        let repeat = repeats.iter().find(|r| r.period == 2 && r.start <= micro && r.end >= micro + 30).unwrap();
+        assert_eq!(repeat.unit.len(), 2);
+        assert!(repeat.copies >= 15.0);
+        assert_eq!(repeat.purity, 1.0);
+
+        let finder = TandemRepeatFinder::new().with_periods(7, 20).with_min_length(40).with_max_mismatch_rate(0.1);
+        let repeats = finder.find(&seq).unwrap();
+        let repeat = repeats.iter().find(|r| r.start <= mini && r.end >= mini + 50).unwrap();
+        assert_eq!(repeat.period, unit.len());
+        assert!(repeat.mismatches >= 2);
+        assert!(repeat.purity < 1.0 && repeat.copies >= 5.0);
+        for repeat in &repeats {
+            let mismatches = (repeat.start..repeat.end - repeat.period)
+                .filter(|&i| seq.chain[i] != seq.chain[i + repeat.period])
+                .count();
+            assert_eq!(mismatches, repeat.mismatches);
+        }
+    }
+
+    #[test]
+    fn inverted_repeats() {
+        use biogarden::processing::patterns::{reverse_complement_substrings, InvertedRepeatFinder};
+        use biogarden::processing::transformers::reverse_complement;
+
+        // Insert a terminator-like hairpin with a mismatch in its stem into a background sequence
+        let background = Sequence::from(&read_sequence("input/linguistic_complexity.fasta").chain[..10000]);
+        let arm = Sequence::from("GCCCGCCTAATGAGCGGGC");
+        let mut right_arm = reverse_complement(&arm);
+        right_arm.chain[5] = b'A';
+        let pos = background.len() / 2;
+        let mut chain = background.chain[..pos].to_vec();
+        chain.extend(&arm.chain);
+        chain.extend(b"TTCG");
+        chain.extend(&right_arm.chain);
+        chain.extend(&background.chain[pos..]);
+        let seq = Sequence::from(chain);
+
+        let finder = InvertedRepeatFinder::new().with_min_stem(15).with_loop(3, 8).with_max_mismatches(2);
+        let repeats = finder.find(&seq).unwrap();
+        let (loop_start, loop_end) = (pos + arm.len(), pos + arm.len() + 4);
+        let hairpin = repeats.iter().find(|r| r.spacer == (loop_start..loop_end)).unwrap();
+        assert!(hairpin.stem >= arm.len() && hairpin.mismatches >= 1);
+        assert_eq!(hairpin.left_arm.end, loop_start);
+        assert_eq!(hairpin.right_arm.start, loop_end);
+        // Exact stems stop at the mismatch
+        let exact = InvertedRepeatFinder::new().with_min_stem(10).with_loop(3, 8).find(&seq).unwrap();
+        assert!(exact.iter().all(|r| r.spacer != (loop_start..loop_end)));
+
+        // Reverse palindromes are inverted repeats without a loop
+        let palindromes = reverse_complement_substrings(&seq, 4, 12);
+        let loopless = InvertedRepeatFinder::new().with_min_stem(2).with_loop(0, 0).find(&seq).unwrap();
+        assert_eq!(loopless.len(), palindromes.iter().filter(|p| p.1 == 4).count());
+        for &(start, len) in &palindromes {
+            let substring = Sequence::from(&seq.chain[start..start + len]);
+            assert_eq!(reverse_complement(&substring), substring);
+        }
+    }
+
+    #[test]
+    fn aho_corasick() {
+        use biogarden::ds::builders::trie::Trie;
+
+        let input = read_sequences("input/find_motif.fasta");
+        let genome = &input[0];
+        let motif = &input[1];
+
+        // Motif together with its suffixes and words overlapping its occurrences
+        let mut words: Vec<Vec<u8>> = (0..motif.len()).map(|i| motif.chain[i..].to_vec()).collect();
+        words.push(motif.chain[..motif.len() / 2].to_vec());
+        words.push(b"CACA".to_vec());
+        words.push(b"ACAC".to_vec());
+        words.push(b"TT".to_vec());
+
+        let mut trie = Trie::new(b"ACGT");
+        trie.build(&words).unwrap();
+        let occurrences = trie.find_all(&genome.chain).unwrap();
+
+        // Occurrences are ordered by their end position
+        let ends: Vec<usize> = occurrences.iter().map(|&(word, start)| start + words[word].len()).collect();
+        assert!(ends.windows(2).all(|w| w[0] <= w[1]));
+
+        // Every word is found at the same positions as by a separate search
+        for (i, word) in words.iter().enumerate() {
+            let mut found: Vec<usize> = occurrences.iter().filter(|&&(w, _)| w == i).map(|&(_, start)| start).collect();
+            found.sort();
+            assert_eq!(found, processing::patterns::find_motif(genome, &Sequence::from(word.clone())));
+        }
+    }
+
+    #[test]
+    fn suffix_array() {
+        use biogarden::ds::builders::suffix_array::SuffixArrayBuilder;
+
+        for seq in &read_sequences("input/find_motif.fasta") {
+            let sa = SuffixArrayBuilder::new().build(seq);
+            let text = sa.text();
+
+            // Suffixes in the order of a naive sort, the terminator sorts before all letters
+            let mut naive: Vec<usize> = (0..text.len()).collect();
+            naive.sort_by_key(|&i| &text[i..]);
+            assert_eq!(sa.sa(), &naive[..]);
+
+            // Common prefixes of neighbouring suffixes and preceding letters
+            let lcp = sa.lcp();
+            for i in 1..naive.len() {
+                let (a, b) = (&text[naive[i - 1]..], &text[naive[i]..]);
+                assert_eq!(lcp[i], a.iter().zip(b).take_while(|(x, y)| x == y).count());
+            }
+            let bwt: Vec<u8> = naive.iter().map(|&i| text[(i + text.len() - 1) % text.len()]).collect();
+            assert_eq!(sa.bwt(), bwt);
+
+            // Search interval covers all occurrences of a substring
+            let pattern = &seq.chain[seq.len() / 2..seq.len() / 2 + 4];
+            let (start, end) = sa.search(pattern);
+            let mut found: Vec<usize> = sa.sa()[start..end].to_vec();
+            found.sort();
+            assert_eq!(found, processing::patterns::find_motif(seq, &Sequence::from(pattern)));
+        }
+    }
+
+    #[test]
+    fn kmer_counter() {
+        use biogarden::processing::kmers::{decode_kmer, KmerCounter};
+        use biogarden::processing::transformers::{k_mer_composition, reverse_complement};
+
+        let seq = read_sequence("input/kmer_composition.fasta");
+        let composition = k_mer_composition(&seq, 4, b"ACGT").unwrap();
+
+        // Plain counts agree with the lexicographic k-mer composition
+        let mut counter = KmerCounter::<u64>::new(4, false).unwrap();
+        counter.count_sequence(&seq);
+        for (key, &count) in composition.iter().enumerate() {
+            assert_eq!(counter.get(&decode_kmer(key as u64, 4).chain), count);
+        }
+        assert_eq!(counter.histogram().iter().sum::<usize>(), counter.len());
+
+        // Canonical counts add up the counts of both orientations, also with wide keys
+        let mut canonical = KmerCounter::<u128>::new(4, true).unwrap();
+        canonical.count_sequence(&seq);
+        for (key, &count) in composition.iter().enumerate() {
+            let kmer = decode_kmer(key as u64, 4);
+            let rc = reverse_complement(&kmer);
+            let expected = if rc == kmer { count } else { count + counter.get(&rc.chain) };
+            assert_eq!(canonical.get(&kmer.chain), expected);
+        }
+
+        // Counting both halves separately and merging equals counting everything at once
+        let (left, right) = (Sequence::from(&seq.chain[..1000]), Sequence::from(&seq.chain[997..]));
+        let mut merged = KmerCounter::<u64>::new(4, false).unwrap();
+        merged.count_sequence(&left);
+        let mut other = KmerCounter::<u64>::new(4, false).unwrap();
+        other.count_sequence(&right);
+        merged.merge(&other).unwrap();
+        assert_eq!(merged.kmers(), counter.kmers());
+    }
+
+    #[test]
+    fn minimizers_and_syncmers() {
+        use biogarden::processing::kmers::{canonical_kmer, encode_kmer, KmerKey};
+        use biogarden::processing::minimizers::{closed_syncmers, hash_kmer, minimizers};
+        use std::collections::BTreeSet;
+
+        let seq = read_sequence("input/kmer_composition.fasta");
+        let (w, k) = (8, 15);
+        let hash = |i: usize| {
+            let key: u64 = encode_kmer(&seq.chain[i..i + k]).unwrap();
+            hash_kmer(canonical_kmer(key, k), u64::mask(k))
+        };
+
+        // Minimizers are the k-mers of smallest hash within every window of `w` k-mers
+        let hashes: Vec<u64> = (0..=seq.len() - k).map(hash).collect();
+        let mut naive = BTreeSet::<usize>::new();
+        for window in 0..=hashes.len() - w {
+            let min = *hashes[window..window + w].iter().min().unwrap();
+            naive.extend((window..window + w).filter(|&i| hashes[i] == min));
+        }
+        let seeds = minimizers(&seq, w, k).unwrap();
+        assert_eq!(seeds.iter().map(|seed| seed.position).collect::<BTreeSet<usize>>(), naive);
+        assert!(seeds.iter().all(|seed| seed.hash == hashes[seed.position]));
+
+        // A mutation only changes closed syncmers overlapping it
+        let s = 5;
+        let mut mutated = seq.clone();
+        mutated.chain[500] = if seq.chain[500] == b'A' { b'C' } else { b'A' };
+        let before = closed_syncmers(&seq, k, s).unwrap();
+        let after = closed_syncmers(&mutated, k, s).unwrap();
+        let distant = |seeds: &[biogarden::processing::minimizers::Seed]| -> Vec<usize> {
+            seeds.iter().map(|seed| seed.position).filter(|&p| p + k <= 500 || p > 500).collect()
+        };
+        assert_eq!(distant(&before), distant(&after));
+        // Every window of `k - s` consecutive k-mers contains a closed syncmer
+        assert!(before.windows(2).all(|pair| pair[1].position - pair[0].position <= k - s));
+    }
+
+    #[test]
+    fn sketch_mash_distance() {
+        use biogarden::analysis::sketch::{Sketch, SketchKind};
+        use biogarden::processing::kmers::KmerCounter;
+        use std::collections::HashSet;
+
+        let genome = read_sequence("input/kmer_composition.fasta");
+        let k = 16;
+        // Substitute every 100th base
+        let mut mutated = genome.clone();
+        for i in (50..mutated.len()).step_by(100) {
+            mutated.chain[i] = match mutated.chain[i] { b'A' => b'C', b'C' => b'G', b'G' => b'T', _ => b'A' };
+        }
+
+        // Sketches large enough to retain every k-mer yield the exact Jaccard index of canonical k-mers
+        let kmers = |seq: &Sequence| {
+            let mut counter = KmerCounter::<u64>::new(k, true).unwrap();
+            counter.count_sequence(seq);
+            counter.iter().map(|(&key, _)| key).collect::<HashSet<u64>>()
+        };
+        let (a, b) = (kmers(&genome), kmers(&mutated));
+        let jaccard = a.intersection(&b).count() as f64 / a.union(&b).count() as f64;
+
+        let kind = SketchKind::BottomK(2 * genome.len());
+        let s1 = Sketch::from_sequence(&genome, k, kind).unwrap();
+        let s2 = Sketch::from_sequence(&mutated, k, kind).unwrap();
+        assert_eq!(s1.len(), a.len());
+        assert!((s1.jaccard(&s2).unwrap() - jaccard).abs() < 1e-12);
+        let distance = -1.0 / k as f64 * (2.0 * jaccard / (1.0 + jaccard)).ln();
+        assert!((s1.mash_distance(&s2).unwrap() - distance).abs() < 1e-12);
+        // Mash distance estimates the substitution rate
+        assert!((distance - 0.01).abs() < 0.005);
+
+        // A fragment is contained in the genome, and survives a write/read round trip
+        let fragment = Sequence::from(&genome.chain[1000..3000]);
+        let kind = SketchKind::FracMinHash(10);
+        let whole = Sketch::from_sequence(&genome, k, kind).unwrap();
+        let part = Sketch::from_sequence(&fragment, k, kind).unwrap();
+        assert_eq!(part.containment(&whole).unwrap(), 1.0);
+        let mut buffer = Vec::<u8>::new();
+        part.write(&mut buffer).unwrap();
+        assert_eq!(Sketch::read(&buffer[..]).unwrap(), part);
+
+        // Hashes above the FracMinHash threshold are rejected
+        let last = buffer.len() - 8;
+        buffer[last..].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(Sketch::read(&buffer[..]).is_err());
+        // As are more hashes than retained by a bottom-k sketch, whose size follows magic number, k and kind
+        let mut buffer = Vec::<u8>::new();
+        s1.write(&mut buffer).unwrap();
+        assert_eq!(Sketch::read(&buffer[..]).unwrap(), s1);
+        buffer[24..32].copy_from_slice(&10_u64.to_le_bytes());
+        assert!(Sketch::read(&buffer[..]).is_err());
+    }
+
+    #[test]
+    fn genetic_code_tables() {
+        use biogarden::processing::genetic_code::GeneticCode;
+
+        let rna = read_sequence("input/translate_rna.fasta");
+        let protein = read_sequence("output/translate_rna.fasta");
+
+        // Standard code reproduces the reference translation up to the first stop codon
+        let standard = GeneticCode::standard().translate(&rna);
+        let end = standard.chain.iter().position(|&c| c == b'*').unwrap_or(standard.len());
+        assert_eq!(Sequence::from(&standard.chain[..end]), protein);
+
+        // Vertebrate mitochondrial code only differs at the reassigned codons
+        let mito = GeneticCode::new(2).unwrap().translate(&rna);
+        assert_eq!(mito.len(), standard.len());
+        for (i, codon) in rna.chain.chunks_exact(3).enumerate() {
+            let expected = match codon {
+                b"UGA" => b'W',
+                b"AGA" | b"AGG" => b'*',
+                b"AUA" => b'M',
+                _ => standard.chain[i],
+            };
+            assert_eq!(mito.chain[i], expected);
+        }
+        assert_ne!(mito, standard);
+
+        // Every table has a start codon, its stop codons translate to `*`
+        for id in GeneticCode::ids() {
+            let code = GeneticCode::new(id).unwrap();
+            assert!(!code.start_codons().is_empty());
+            for codon in code.stop_codons() {
+                assert_eq!(code.translate_codon(&codon.chain), Some(b'*'));
+            }
+        }
+    }
+
+    #[test]
+    fn fm_index_serialisation() {
+        use biogarden::ds::fm_index::FmIndex;
+
+        let input = read_sequences("input/longest_common_substring.fasta");
+        let index = FmIndex::from_tile(&input, 8);
+        let path = std::env::temp_dir().join("biogarden_fm_index.bin");
+        index.to_file(&path).unwrap();
+        let loaded = FmIndex::from_file(&path).unwrap();
+
+        // Occurrences are found at the same positions before and after saving and loading
+        for seq in &input {
+            for pattern in seq.chain.chunks(7) {
+                assert_eq!(loaded.locate(pattern), index.locate(pattern));
+                assert_eq!(loaded.count(pattern), index.count(pattern));
+            }
+        }
+
+        // Truncated and corrupt files are rejected
+        let mut buffer = Vec::<u8>::new();
+        index.write(&mut buffer).unwrap();
+        assert!(FmIndex::read(&buffer[..buffer.len() - 5]).is_err());
+        // Length of the BWT, following magic number and sample rate
+        let mut corrupt = buffer.clone();
+        corrupt[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(FmIndex::read(&corrupt[..]).is_err());
+        // Offset of the last sequence
+        let mut corrupt = buffer.clone();
+        corrupt[buffer.len() - 8..].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(FmIndex::read(&corrupt[..]).is_err());
+        // Offsets of the first and the last sequence, which must start at 0 and be increasing
+        let first = buffer.len() - 8 * input.len();
+        let mut corrupt = buffer.clone();
+        corrupt[first..first + 8].copy_from_slice(&1_u64.to_le_bytes());
+        assert!(FmIndex::read(&corrupt[..]).is_err());
+        let mut corrupt = buffer.clone();
+        corrupt[buffer.len() - 8..].copy_from_slice(&0_u64.to_le_bytes());
+        assert!(FmIndex::read(&corrupt[..]).is_err());
+        assert!(FmIndex::read(&buffer[..]).is_ok());
+    }
+
+    #[test]
+    fn debruijn_repeated_kmers() {
+        use biogarden::ds::algo::euler;
+        use biogarden::ds::builders::debruijn::DeBruijnBuilder;
+
+        // Genome containing a repeat longer than the k-mers
+        let genome = read_sequence("output/assembly_genome.fasta");
+        let mut chain = genome.chain[..600].to_vec();
+        chain.extend_from_slice(&genome.chain[100..200]);
+        chain.extend_from_slice(&genome.chain[600..]);
+        let mut genomes = Tile::new();
+        genomes.push(Sequence::from(chain.clone()));
+        let k = 31;
+
+        // Every occurrence of a k-mer is an edge, an Eulerian path spells a sequence of the same length and k-mers
+        let graph = DeBruijnBuilder::new().with_k(k).build(&genomes);
+        assert_eq!(graph.edge_count(), chain.len() - k + 1);
+        let path = euler::eulerian_path(&graph).unwrap();
+        let spelled = euler::spell_path(&graph, &path.nodes);
+        let sorted_kmers = |seq: &[u8]| {
+            let mut kmers: Vec<Vec<u8>> = seq.windows(k).map(|kmer| kmer.to_vec()).collect();
+            kmers.sort();
+            kmers
+        };
+        assert_eq!(sorted_kmers(&spelled.chain), sorted_kmers(&chain));
+
+        // Counting merges the repeated k-mers into single edges
+        let counted = DeBruijnBuilder::new().with_k(k).with_counts(true).build(&genomes);
+        let mut counts = HashMap::<&[u8], usize>::new();
+        chain.windows(k).for_each(|kmer| *counts.entry(kmer).or_insert(0) += 1);
+        assert_eq!(counted.edge_count(), counts.len());
+        // K-mers within the repeat, and possibly some at its boundaries
+        let repeated = 100 - k + 1;
+        assert!(counts.values().filter(|&&count| count == 2).count() >= repeated);
+        for e in counted.edges() {
+            let edge = counted.get_edge(e);
+            let mut kmer = counted.get_node(&edge.start).data.chain.clone();
+            kmer.push(*counted.get_node(&edge.end).data.chain.last().unwrap());
+            assert_eq!(edge.data, Some(counts[&kmer[..]]));
+        }
+        assert!(graph.edges().all(|e| graph.get_edge(e).data.is_none()));
+    }
+
+    #[test]
+    fn graph_compaction() {
+        use biogarden::ds::graph::{EdgeId, NodeId};
+
+        let reads = read_sequences("input/assembly_reads.fasta");
+        let mut graph = biogarden::ds::builders::debruijn::DeBruijnBuilder::new()
+            .with_k(31)
+            .with_counts(true)
+            .build(&reads);
+        let labels = |graph: &biogarden::ds::graph::Graph<Sequence, usize>, edge: &u64| {
+            let edge = graph.get_edge(edge);
+            (graph.get_node(&edge.start).data.clone(), graph.get_node(&edge.end).data.clone(), edge.data)
+        };
+        let (nodes, edges) = (graph.node_bound(), graph.edge_bound());
+
+        // Remove every third node and every fifth remaining edge, leaving tombstones
+        let removed_nodes: Vec<u64> = graph.nodes().cloned().filter(|id| id % 3 == 0).collect();
+        removed_nodes.iter().for_each(|&id| { graph.remove_node(id); });
+        let removed_edges: Vec<u64> = graph.edges().cloned().filter(|id| id % 5 == 0).collect();
+        removed_edges.iter().for_each(|id| { graph.remove_edge(id); });
+        assert_eq!((graph.node_bound(), graph.edge_bound()), (nodes, edges));
+        assert_eq!(graph.node_count(), nodes - removed_nodes.len());
+        assert!(removed_nodes.iter().all(|id| !graph.contains_node(id) && graph.node(id.into()).is_none()));
+        assert!(removed_edges.iter().all(|id| !graph.contains_edge(id)));
+
+        let remaining_nodes: Vec<(u64, Sequence)> = graph.nodes().map(|id| (*id, graph.get_node(id).data.clone())).collect();
+        let remaining_edges: Vec<(u64, _)> = graph.edges().map(|id| (*id, labels(&graph, id))).collect();
+        assert!(remaining_edges.iter().all(|(id, _)| {
+            let edge = graph.get_edge(id);
+            graph.has_edge(&edge.start, &edge.end).is_ok()
+        }));
+
+        // Compact the storage, ids of remaining nodes and edges are remapped in order
+        let ids = graph.compact();
+        assert_eq!((graph.node_bound(), graph.node_count()), (remaining_nodes.len(), remaining_nodes.len()));
+        assert_eq!((graph.edge_bound(), graph.edge_count()), (remaining_edges.len(), remaining_edges.len()));
+        assert!(removed_nodes.iter().all(|id| ids.node(id).is_none()));
+        assert!(removed_edges.iter().all(|id| ids.edge(id).is_none()));
+        assert_eq!(ids.node(nodes as u64), None);
+
+        for (new_id, (old_id, data)) in remaining_nodes.iter().enumerate() {
+            assert_eq!(ids.node(old_id), Some(NodeId(new_id as u64)));
+            assert_eq!(&graph.get_node(&(new_id as u64)).data, data);
+        }
+        for (new_id, (old_id, edge_labels)) in remaining_edges.iter().enumerate() {
+            let new_id = new_id as u64;
+            assert_eq!(ids.edge(old_id), Some(EdgeId(new_id)));
+            assert_eq!(&labels(&graph, &new_id), edge_labels);
+            let edge = graph.get_edge(&new_id);
+            assert!(graph.get_node(&edge.start).outgoing().contains(&new_id));
+            assert!(graph.get_node(&edge.end).incoming().contains(&new_id));
+            assert_eq!(graph.get_edge(&graph.has_edge(&edge.start, &edge.end).unwrap()).end, edge.end);
+        }
+    }
+
+    #[test]
+    fn degenerate_patterns() {
+        use biogarden::ds::sequence::Strand;
+        use processing::patterns::{find_pattern, Pattern};
+
+        // Letters accepted by an IUPAC code
+        let iupac = |code: u8| -> &'static [u8] {
+            match code {
+                b'A' => b"A", b'C' => b"C", b'G' => b"G", b'T' => b"T",
+                b'R' => b"AG", b'Y' => b"CT", b'S' => b"CG", b'W' => b"AT", b'K' => b"GT", b'M' => b"AC",
+                b'B' => b"CGT", b'D' => b"AGT", b'H' => b"ACT", b'V' => b"ACG", _ => b"ACGT",
+            }
+        };
+        let complement = |c: u8| match c { b'A' => b'T', b'T' => b'A', b'C' => b'G', _ => b'C' };
+
+        // Soft-mask the second half of the genome, matching is case-insensitive
+        let mut genome = read_sequence("output/assembly_genome.fasta");
+        let n = genome.len();
+        genome.chain[n / 2..].make_ascii_lowercase();
+        let upper: Vec<u8> = genome.chain.to_ascii_uppercase();
+
+        for code in ["TATAWR", "GGNCC", "RYNNKMB", "GATC"] {
+            let pattern = Pattern::from_iupac(code).unwrap();
+            let m = code.len();
+            let mut expected = vec![];
+            for (pos, window) in upper.windows(m).enumerate() {
+                if window.iter().zip(code.bytes()).all(|(c, p)| iupac(p).contains(c)) {
+                    expected.push((pos, Strand::Forward));
+                }
+                if window.iter().rev().zip(code.bytes()).all(|(&c, p)| iupac(p).contains(&complement(c))) {
+                    expected.push((pos, Strand::Reverse));
+                }
+            }
+            assert!(!expected.is_empty());
+            assert_eq!(find_pattern(&genome, &pattern, true).unwrap(), expected);
+            let forward: Vec<(usize, Strand)> = expected.iter().cloned().filter(|&(_, s)| s == Strand::Forward).collect();
+            assert_eq!(find_pattern(&genome, &pattern, false).unwrap(), forward);
+        }
+
+        // Protein derived from the overlapping triplets of the genome
+        let amino_acids = b"ACDEFGHIKLMNPQRSTVWY";
+        let protein: Vec<u8> = upper.windows(3)
+            .map(|codon| amino_acids[codon.iter().fold(0, |h, &c| h * 7 + c as usize) % 20])
+            .collect();
+        let protein = Sequence::from(protein);
+
+        // Naive backtracking over all repetition counts, elements given as (letters, excluded, min, max)
+        type Element = (&'static [u8], bool, usize, usize);
+        fn naive(seq: &[u8], pos: usize, elements: &[Element]) -> bool {
+            let Some(&(letters, excluded, min, max)) = elements.first() else {
+                return true;
+            };
+            (min..=max).any(|count| {
+                pos + count <= seq.len()
+                    && seq[pos..pos + count].iter().all(|c| letters.contains(c) != excluded)
+                    && naive(seq, pos + count, &elements[1..])
+            })
+        }
+        let cases: Vec<(&str, Vec<Element>)> = vec![
+            ("C-x(2,4)-C-x(3)-[LIVMFYWC]", vec![(b"C", false, 1, 1), (b"", true, 2, 4), (b"C", false, 1, 1),
+                                                (b"", true, 3, 3), (b"LIVMFYWC", false, 1, 1)]),
+            ("N-{P}-[ST]-{P}", vec![(b"N", false, 1, 1), (b"P", true, 1, 1), (b"ST", false, 1, 1), (b"P", true, 1, 1)]),
+            ("G-x(2,5)-[ST]-x(2,5)-{P}-x(2,5)-K", vec![(b"G", false, 1, 1), (b"", true, 2, 5), (b"ST", false, 1, 1),
+                                                       (b"", true, 2, 5), (b"P", true, 1, 1), (b"", true, 2, 5),
+                                                       (b"K", false, 1, 1)]),
+        ];
+        for (notation, elements) in cases {
+            let pattern = Pattern::from_prosite(notation).unwrap();
+            let expected: Vec<(usize, Strand)> = (0..protein.len())
+                .filter(|&pos| naive(&protein.chain, pos, &elements))
+                .map(|pos| (pos, Strand::Forward))
+                .collect();
+            assert!(!expected.is_empty());
+            assert_eq!(find_pattern(&protein, &pattern, false).unwrap(), expected);
+            // Protein patterns cannot be reverse complemented
+            assert!(find_pattern(&protein, &pattern, true).is_err());
+        }
+    }
 }